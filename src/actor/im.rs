@@ -1,25 +1,72 @@
-use super::{Task, Update};
+use super::{Gate, NonFinite, Progress, SimClock, Task, Update};
 use crate::{io::*, ActorError, ActorOutputBuilder, Result, Who};
 use async_trait::async_trait;
 use futures::future::join_all;
-use std::{fmt, ops::DerefMut, sync::Arc};
-use tokio::sync::Mutex;
+use std::{
+    any::Any,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{oneshot, Mutex};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputKind {
+    Bootstrap,
+    Regular,
+    Throttled,
+}
+#[derive(Debug)]
+pub struct PlainInput {
+    /// Data UID type
+    pub data: String,
+    /// Identifier of the input channel
+    pub channel: usize,
+    /// Input with a timeout (see [IntoOptionalInputs](crate::IntoOptionalInputs))
+    pub optional: bool,
+}
 #[derive(Debug)]
-pub enum PlainOutput {
-    Bootstrap(String),
-    Regular(String),
+pub struct PlainOutput {
+    pub kind: OutputKind,
+    /// Data UID type
+    pub data: String,
+    /// Identifiers of the output channels
+    pub channels: Vec<usize>,
+    /// Channels capacity, [usize::MAX] if unbounded
+    pub capacity: usize,
+    /// Output label
+    pub label: Option<String>,
 }
 #[derive(Debug)]
 pub struct PlainActor {
     pub client: String,
+    pub client_type: String,
     pub inputs_rate: usize,
     pub outputs_rate: usize,
-    pub inputs: Option<Vec<String>>,
+    pub inputs: Option<Vec<PlainInput>>,
     pub outputs: Option<Vec<PlainOutput>>,
+    pub fractional_rates: bool,
 }
 
-/// Actor model implementation
+// Closure run on the client before the actor loop starts
+type OnStart<C> = Box<dyn FnOnce(&mut C) + Send + Sync>;
+
+/**
+Actor model implementation
+
+An [Actor] always owns a client: it can only be created from a client with either [Actor::new] or [From],
+so a [Model](crate::model::Model) cannot hold an actor without a client.
+
+# Example
+An actor cannot be built without a client
+```compile_fail
+use dos_actors::prelude::*;
+let actor: Actor<Logging<f64>> = Actor::new();
+```
+*/
 pub struct Actor<C, const NI: usize = 1, const NO: usize = 1>
 where
     C: Update + Send,
@@ -28,6 +75,18 @@ where
     pub(crate) outputs: Option<Vec<Box<dyn OutputObject>>>,
     pub(crate) client: Arc<Mutex<C>>,
     name: Option<String>,
+    coalescing: bool,
+    on_complete: Option<oneshot::Sender<()>>,
+    on_start: Option<OnStart<C>>,
+    steps: Arc<AtomicUsize>,
+    progress: Arc<Progress>,
+    sampling_frequency_hz: Option<f64>,
+    seed: Option<u64>,
+    gate: Arc<Gate>,
+    nan_guard: Option<flume::Sender<NonFinite>>,
+    bypass: Option<Arc<AtomicBool>>,
+    fractional: bool,
+    phase: usize,
 }
 
 impl<C, const NI: usize, const NO: usize> From<&Actor<C, NI, NO>> for PlainActor
@@ -35,27 +94,41 @@ where
     C: Update + Send,
 {
     fn from(actor: &Actor<C, NI, NO>) -> Self {
-        use PlainOutput::*;
+        use OutputKind::*;
         Self {
             client: actor.name.as_ref().unwrap_or(&actor.who()).to_owned(),
+            client_type: std::any::type_name::<C>().to_string(),
             inputs_rate: NI,
             outputs_rate: NO,
-            inputs: actor
-                .inputs
-                .as_ref()
-                .map(|inputs| inputs.iter().map(|o| o.who()).collect()),
+            inputs: actor.inputs.as_ref().map(|inputs| {
+                inputs
+                    .iter()
+                    .map(|i| PlainInput {
+                        data: i.who(),
+                        channel: i.channel_id(),
+                        optional: i.is_optional(),
+                    })
+                    .collect()
+            }),
             outputs: actor.outputs.as_ref().map(|outputs| {
                 outputs
                     .iter()
-                    .map(|o| {
-                        if o.bootstrap() {
-                            Bootstrap(o.who())
+                    .map(|o| PlainOutput {
+                        kind: if o.bootstrap() {
+                            Bootstrap
+                        } else if o.throttle().is_some() {
+                            Throttled
                         } else {
-                            Regular(o.who())
-                        }
+                            Regular
+                        },
+                        data: o.who(),
+                        channels: (0..o.len()).map(|k| o.channel_id(k)).collect(),
+                        capacity: o.capacity(),
+                        label: o.label(),
                     })
                     .collect()
             }),
+            fractional_rates: actor.fractional,
         }
     }
 }
@@ -131,23 +204,272 @@ where
             outputs: None,
             client,
             name: None,
+            coalescing: false,
+            on_complete: None,
+            on_start: None,
+            steps: Arc::new(AtomicUsize::new(0)),
+            progress: Default::default(),
+            sampling_frequency_hz: None,
+            seed: None,
+            gate: Default::default(),
+            nan_guard: None,
+            bypass: None,
+            fractional: false,
+            phase: 0,
+        }
+    }
+    /// Sets the actor name
+    #[cfg(feature = "serde")]
+    pub(crate) fn named<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+    /**
+    Sets the actor inputs to only read the most recent of all the queued samples
+
+    The intermediate samples are discarded.
+    The inputs added to the actor after this call are coalescing inputs.
+    It is meant for best-effort actors such as visualization or logging,
+    and it should not be used within control loops that require every sample.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 10)
+        .signals(Signal::Ramp { a: 1., b: 0. })
+        .into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone()).coalescing();
+    source
+        .add_output()
+        .unbounded()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    source.task().await;
+    drop(source);
+    sink.task().await;
+    assert_eq!(*logging.lock().await.as_slice(), [9f64]);
+    # });
+    ```
+    */
+    pub fn coalescing(mut self) -> Self {
+        self.coalescing = true;
+        self
+    }
+    /**
+    Notifies the completion of the actor loop
+
+    A message is sent with `tx` as soon as the actor loop ends,
+    independently of the completion of the other actors of the model
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    use std::time::Instant;
+    use tokio::sync::oneshot;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let (tx, rx) = oneshot::channel();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx()).on_complete(tx);
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let mut long_source: Initiator<_> = Signals::new(1, 10_000).into();
+    let mut long_sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    long_source.add_output().build::<Vec<f64>, Sig>().into_input(&mut long_sink);
+    let model = Model::new(vec![
+        Box::new(source),
+        Box::new(sink),
+        Box::new(long_source),
+        Box::new(long_sink),
+    ])
+    .check()?
+    .run();
+    let (completed, joined) = tokio::join!(
+        async {
+            rx.await.unwrap();
+            Instant::now()
+        },
+        async {
+            model.wait().await.unwrap();
+            Instant::now()
+        }
+    );
+    assert!(completed < joined);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn on_complete(mut self, tx: oneshot::Sender<()>) -> Self {
+        self.on_complete = Some(tx);
+        self
+    }
+    /**
+    Runs `f` on the client once, after the bootstrapped outputs have been sent and before the actor loop starts
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{io::*, prelude::*, Update};
+    use std::sync::Arc;
+    #[derive(Default)]
+    struct Plant {
+        n_write: usize,
+        n_update: usize,
+        on_start: Vec<(usize, usize)>,
+    }
+    impl Update for Plant {
+        fn update(&mut self) {
+            self.n_update += 1;
+        }
+    }
+    impl<U> Read<Vec<f64>, U> for Plant {
+        fn read(&mut self, _data: Arc<Data<Vec<f64>, U>>) {}
+    }
+    impl<U> Write<Vec<f64>, U> for Plant {
+        fn write(&mut self) -> Option<Arc<Data<Vec<f64>, U>>> {
+            self.n_write += 1;
+            Some(Arc::new(Data::new(vec![0f64])))
+        }
+    }
+    enum U {};
+    enum Y {};
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let plant = Plant::default().into_arcx();
+    let mut actor = Actor::<_>::new(plant.clone())
+        .on_start(|plant: &mut Plant| plant.on_start.push((plant.n_write, plant.n_update)));
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, U>().into_input(&mut actor);
+    actor
+        .add_output()
+        .bootstrap()
+        .build::<Vec<f64>, Y>()
+        .into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(actor), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert_eq!(plant.lock().await.on_start, vec![(1, 0)]);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn on_start<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut C) + Send + Sync + 'static,
+    {
+        self.on_start = Some(Box::new(f));
+        self
+    }
+    /**
+    Bypasses the client while the `bypass` flag is set
+
+    The actor keeps receiving and sending data but the client neither reads the inputs nor is [update](Update::update)d,
+    and the outputs hold the last data written by the client before the bypass.
+    The flag is shared with the caller and can be toggled at any time while the model is running,
+    it is read once at the start of each iteration of the actor loop so a whole iteration is either bypassed or not.
+
+    # Example
+    An integrator stops integrating while bypassed
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{clients::Integrator, prelude::*};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+    enum U {};
+    enum Y {};
+    let mut source: Initiator<_> = Signals::new(1, 6).signals(Signal::Constant(1.)).into();
+    let bypass = Arc::new(AtomicBool::new(false));
+    let mut integrator: Actor<_> = Actor::from(Integrator::<f64, U>::new(1).gain(0.5))
+        .bypassable(bypass.clone());
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, U>().into_input(&mut integrator);
+    integrator.add_output().build::<Vec<f64>, Y>().into_input(&mut sink);
+    let mut stepper = Model::new(vec![Box::new(source), Box::new(integrator), Box::new(sink)])
+        .check()?
+        .into_stepper()?;
+    for step in 0..6 {
+        bypass.store((2..4).contains(&step), Ordering::Relaxed);
+        stepper.step().await?;
+    }
+    assert_eq!(*logging.lock().await.as_slice(), [-0.5, -1., -1., -1., -1.5, -2.]);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn bypassable(mut self, bypass: Arc<AtomicBool>) -> Self {
+        self.bypass = Some(bypass);
+        self
+    }
+    /**
+    Allows the inputs rate `NI` and the outputs rate `NO` not to be integer multiples of each other
+
+    After each input, the actor sends as many outputs as there are output sampling times within the input period
+    (see [FractionalSampler](crate::clients::FractionalSampler)).
+    Without it, [Model::check](crate::model::Model::check) rejects an actor with such rates:
+    ```
+    use dos_actors::{clients::FractionalSampler, model::ModelError, prelude::*};
+    enum Sig {};
+    let mut source: Initiator<_, 2> = Signals::new(1, 100).into();
+    let mut sampler: Actor<_, 2, 3> = FractionalSampler::<Vec<f64>, Sig>::default().into();
+    let mut sink = Terminator::<_, 3>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+    sampler.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let check = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)]).check();
+    assert!(matches!(check, Err(ModelError::NonIntegerRateRatio(_, 2, 3))));
+    ```
+    */
+    pub fn fractional(mut self) -> Self {
+        self.fractional = true;
+        self
+    }
+    // Checks if the client is bypassed
+    fn is_bypassed(&self) -> bool {
+        self.bypass
+            .as_ref()
+            .is_some_and(|bypass| bypass.load(Ordering::Relaxed))
+    }
+    // Seeds the client random number generators
+    async fn seed_client(&mut self) {
+        if let Some(seed) = self.seed.take() {
+            self.client.lock().await.seed(seed);
+        }
+    }
+    /// Runs the [on_start](Actor::on_start) closure
+    async fn start(&mut self) {
+        if let Some(f) = self.on_start.take() {
+            f(&mut *self.client.lock().await);
         }
     }
     /// Gathers all the inputs from other [Actor] outputs
-    async fn collect(&mut self) -> Result<()> {
+    ///
+    /// The data of the inputs are passed to the client, in the order the inputs were added, once all of them have been received,
+    /// unless the client is `bypass`ed
+    async fn collect(&mut self, bypass: bool) -> Result<()> {
         if let Some(inputs) = &mut self.inputs {
             let futures: Vec<_> = inputs.iter_mut().map(|input| input.recv()).collect();
             join_all(futures)
                 .await
                 .into_iter()
                 .collect::<Result<Vec<_>>>()?;
+            for input in inputs.iter_mut() {
+                input.read(bypass).await;
+            }
         }
         Ok(())
     }
-    /// Sends the outputs to other [Actor] inputs
-    async fn distribute(&mut self) -> Result<&Self> {
+    /// Sends the outputs to other [Actor] inputs, the last outputs are held if the client is `bypass`ed
+    async fn distribute(&mut self, bypass: bool) -> Result<&Self> {
         if let Some(outputs) = &mut self.outputs {
-            let futures: Vec<_> = outputs.iter_mut().map(|output| output.send()).collect();
+            let futures: Vec<_> = outputs
+                .iter_mut()
+                .map(|output| if bypass { output.hold() } else { output.send() })
+                .collect();
             join_all(futures)
                 .await
                 .into_iter()
@@ -155,6 +477,20 @@ where
         }
         Ok(self)
     }
+    /**
+    Sends the outputs which sampling times fall within the period of the last input
+
+    For inputs and outputs rates that are not integer multiples of each other,
+    the phase is the offset of the next output sampling time from the sampling time of the last input
+    */
+    async fn distribute_fractional(&mut self, bypass: bool) -> Result<()> {
+        while self.phase < NI {
+            self.distribute(bypass).await?;
+            self.phase += NO;
+        }
+        self.phase -= NI;
+        Ok(())
+    }
     /// Invokes outputs senders
     async fn bootstrap(&mut self) -> Result<&mut Self> {
         if let Some(outputs) = &mut self.outputs {
@@ -162,7 +498,7 @@ where
                 let futures: Vec<_> = outputs
                     .iter_mut()
                     .filter(|output| output.bootstrap())
-                    .map(|output| output.send())
+                    .map(|output| output.send_bootstrap())
                     .collect();
                 join_all(futures)
                     .await
@@ -195,55 +531,94 @@ where
     }
     /// Run the actor loop
     async fn task(&mut self) {
-        match self.bootstrap().await {
-            Err(e) => crate::print_error(format!("{} bootstrapping failed", Who::who(self)), &e),
+        match self.init().await {
+            Err(e) => {
+                crate::print_error(format!("{} bootstrapping failed", Who::who(self)), &e);
+                self.complete(Ok(()));
+            }
             Ok(_) => {
-                if let Err(e) = self.async_run().await {
-                    crate::print_error(format!("{} loop ended", Who::who(self)), &e);
-                }
+                let result = self.async_run().await;
+                self.complete(result);
+            }
+        }
+    }
+    fn complete(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            if let (ActorError::NonFinite(output), Some(tx)) = (&e, &self.nan_guard) {
+                let _ = tx.send(NonFinite {
+                    actor: self.as_plain().client,
+                    output: output.clone(),
+                    step: self.progress.iterations().saturating_sub(1),
+                });
             }
+            crate::print_error(format!("{} loop ended", Who::who(self)), &e);
+        }
+        if let Some(tx) = self.on_complete.take() {
+            let _ = tx.send(());
         }
     }
     /// Starts the actor infinite loop
     async fn async_run(&mut self) -> Result<()> {
+        loop {
+            self.gate.wait().await;
+            if self.step().await? {
+                break Ok(());
+            }
+        }
+    }
+    async fn init(&mut self) -> Result<()> {
+        self.seed_client().await;
+        self.bootstrap().await?;
+        self.start().await;
+        Ok(())
+    }
+    async fn step(&mut self) -> Result<bool> {
+        let bypass = self.is_bypassed();
         match (self.inputs.as_ref(), self.outputs.as_ref()) {
             (Some(_), Some(_)) => {
-                if NO >= NI {
+                if self.fractional {
+                    // Fractional rate transition
+                    self.collect(bypass).await?;
+                    if self.update_client(bypass).await? {
+                        return Ok(true);
+                    }
+                    self.distribute_fractional(bypass).await?;
+                } else if NO >= NI {
                     // Decimation
-                    loop {
-                        for _ in 0..NO / NI {
-                            self.collect().await?;
-                            self.client.lock().await.deref_mut().update();
+                    for _ in 0..NO / NI {
+                        self.collect(bypass).await?;
+                        if self.update_client(bypass).await? {
+                            return Ok(true);
                         }
-                        self.distribute().await?;
                     }
+                    self.distribute(bypass).await?;
                 } else {
                     // Upsampling
-                    loop {
-                        self.collect().await?;
-                        self.client.lock().await.deref_mut().update();
-                        for _ in 0..NI / NO {
-                            self.distribute().await?;
-                        }
+                    self.collect(bypass).await?;
+                    if self.update_client(bypass).await? {
+                        return Ok(true);
+                    }
+                    for _ in 0..NI / NO {
+                        self.distribute(bypass).await?;
                     }
                 }
             }
-            (None, Some(_)) => loop {
+            (None, Some(_)) => {
                 // Initiator
-                self.client.lock().await.deref_mut().update();
-                self.distribute().await?;
-            },
-            (Some(_), None) => loop {
-                // Terminator
-                match self.collect().await {
-                    Ok(_) => {
-                        self.client.lock().await.deref_mut().update();
-                    }
-                    Err(e) => break Err(e),
+                if self.update_client(bypass).await? {
+                    return Ok(true);
                 }
-            },
-            (None, None) => Ok(()),
+                self.distribute(bypass).await?;
+                self.steps.fetch_add(1, Ordering::Relaxed);
+            }
+            (Some(_), None) => {
+                // Terminator
+                self.collect(bypass).await?;
+                return self.update_client(bypass).await;
+            }
+            (None, None) => return Ok(true),
         }
+        Ok(false)
     }
     fn check_inputs(&self) -> Result<()> {
         match self.inputs {
@@ -256,12 +631,112 @@ where
         match self.outputs {
             Some(_) if NO == 0 => Err(ActorError::SomeOutputsZeroRate(Who::who(self))),
             None if NO > 0 => Err(ActorError::NoOutputsPositiveRate(Who::who(self))),
+            Some(ref outputs) => outputs
+                .iter()
+                .find(|output| output.bootstrap() && output.throttle().is_some())
+                .map_or(Ok(()), |output| {
+                    Err(ActorError::ThrottledBootstrap(output.who()))
+                }),
             _ => Ok(()),
         }
     }
     fn as_plain(&self) -> PlainActor {
         self.into()
     }
+    fn resize_outputs(&mut self, ticks: Option<usize>) -> Vec<(usize, Box<dyn Any + Send>)> {
+        let capacity = match ticks {
+            _ if NO == 0 => 1,
+            Some(ticks) => ticks.div_ceil(NO) + 1,
+            None => NI.div_ceil(NO).max(1),
+        };
+        self.outputs
+            .iter_mut()
+            .flatten()
+            .flat_map(|output| output.resize(capacity))
+            .collect()
+    }
+    fn rewire_input(&mut self, id: usize, rx: Box<dyn Any + Send>) -> Option<Box<dyn Any + Send>> {
+        let mut rx = Some(rx);
+        for input in self.inputs.iter_mut().flatten() {
+            rx = input.rewire(id, rx.take()?);
+        }
+        rx
+    }
+    fn rewire_dangling_input(
+        &mut self,
+        id: usize,
+        rx: Box<dyn Any + Send>,
+    ) -> Option<Box<dyn Any + Send>> {
+        let mut rx = Some(rx);
+        for input in self.inputs.iter_mut().flatten() {
+            rx = input.rewire_dangling(id, rx.take()?);
+        }
+        rx
+    }
+    fn prune_outputs(&mut self) {
+        for output in self.outputs.iter_mut().flatten() {
+            output.prune();
+        }
+    }
+    fn progress(&self) -> Arc<Progress> {
+        self.progress.clone()
+    }
+    fn steps(&self) -> Option<Arc<AtomicUsize>> {
+        match (self.inputs.as_ref(), self.outputs.as_ref()) {
+            (None, Some(_)) => Some(self.steps.clone()),
+            _ => None,
+        }
+    }
+    fn clock(&mut self, sampling_frequency_hz: f64) {
+        self.sampling_frequency_hz = Some(sampling_frequency_hz);
+    }
+    fn seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+    fn gate(&mut self, gate: Arc<Gate>) {
+        self.gate = gate;
+    }
+    fn nan_guard(&mut self, tx: flume::Sender<NonFinite>) {
+        self.outputs
+            .iter_mut()
+            .flatten()
+            .for_each(|output| output.nan_guard());
+        self.nan_guard = Some(tx);
+    }
+}
+
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    /**
+    Updates the client, the client clock is ticked first if the actor has a clock
+    and the client update is skipped if the actor is [bypass](Actor::bypassable)ed
+
+    Returns `true` if the client is [finished](Update::finished)
+    or the error [ActorError::Client] if the client has [failed](Update::failure)
+    */
+    async fn update_client(&self, bypass: bool) -> Result<bool> {
+        let mut client = self.client.lock().await;
+        if let Some(sampling_frequency_hz) = self.sampling_frequency_hz {
+            let period = if NI > 0 { NI } else { NO };
+            client.tick(&SimClock::new(
+                self.progress.iterations() * period,
+                sampling_frequency_hz,
+            ));
+        }
+        if !bypass {
+            client.update();
+        }
+        let failure = client.failure();
+        let finished = client.finished();
+        drop(client);
+        self.progress.tick();
+        match failure {
+            Some(message) => Err(ActorError::Client(Who::who(self), message)),
+            None => Ok(finished),
+        }
+    }
 }
 
 impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
@@ -272,19 +747,79 @@ where
     pub fn add_output(&mut self) -> (&mut Actor<C, NI, NO>, ActorOutputBuilder) {
         (self, ActorOutputBuilder::new(1))
     }
-    /// Adds an output to an actor
-    pub(crate) fn add_input<T, U>(&mut self, rx: flume::Receiver<Arc<Data<T, U>>>)
-    where
+    // Appends an input to the actor inputs
+    fn push_input(&mut self, input: Box<dyn InputObject>) {
+        self.inputs.get_or_insert_with(Vec::new).push(input);
+    }
+    /// Adds an input to an actor, `ordered` validates the order of the data steps
+    pub(crate) fn add_input<T, U>(
+        &mut self,
+        rx: Receiver<Arc<Data<T, U>>>,
+        id: usize,
+        ordered: bool,
+    ) where
         C: Read<T, U>,
         T: 'static + Send + Sync,
         U: 'static + Send + Sync,
     {
-        let input: Input<C, T, U, NI> = Input::new(rx, self.client.clone());
-        if let Some(ref mut inputs) = self.inputs {
-            inputs.push(Box::new(input));
-        } else {
-            self.inputs = Some(vec![Box::new(input)]);
-        }
+        let input: Input<C, T, U, NI> = Input::new(rx, id, self.client.clone())
+            .coalescing(self.coalescing)
+            .ordered(ordered);
+        self.push_input(Box::new(input));
+    }
+    /// Adds an input to an actor that validates the length `n` of the data, `ordered` validates the order of the data steps
+    pub(crate) fn add_sized_input<T, U>(
+        &mut self,
+        rx: Receiver<Arc<Data<Vec<T>, U>>>,
+        id: usize,
+        n: usize,
+        ordered: bool,
+    ) where
+        C: Read<Vec<T>, U>,
+        T: 'static + Send + Sync,
+        U: 'static + Send + Sync,
+    {
+        let input: Input<C, Vec<T>, U, NI> = Input::new(rx, id, self.client.clone())
+            .coalescing(self.coalescing)
+            .size(n, Vec::len)
+            .ordered(ordered);
+        self.push_input(Box::new(input));
+    }
+}
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    /// Returns the identifier of the channel of sender #`k` of the last output
+    pub(crate) fn channel_id(&self, k: usize) -> usize {
+        self.outputs
+            .as_ref()
+            .and_then(|outputs| outputs.last())
+            .map(|output| output.channel_id(k))
+            .expect("the actor has no outputs")
+    }
+    /// Returns `true` if the inputs connected to the last output validate the order of the data
+    pub(crate) fn ordered_output(&self) -> bool {
+        self.outputs
+            .as_ref()
+            .and_then(|outputs| outputs.last())
+            .is_some_and(|output| output.ordered())
+    }
+    /// Adds an optional input to an actor, `ordered` validates the order of the data steps
+    pub(crate) fn add_optional_input<T, U>(
+        &mut self,
+        rx: Receiver<Arc<Data<T, U>>>,
+        id: usize,
+        timeout: Duration,
+        ordered: bool,
+    ) where
+        C: ReadOption<T, U>,
+        T: 'static + Send + Sync,
+        U: 'static + Send + Sync,
+    {
+        let input: OptionalInput<C, T, U, NI> =
+            OptionalInput::new(rx, id, self.client.clone(), timeout).ordered(ordered);
+        self.push_input(Box::new(input));
     }
 }
 impl<C, const NI: usize, const NO: usize> Drop for Actor<C, NI, NO>
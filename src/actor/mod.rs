@@ -40,12 +40,171 @@ let sink = Terminator::<_>::new(logging.clone());
 
 use crate::Result;
 use async_trait::async_trait;
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
 mod im;
-pub use im::{Actor, PlainActor, PlainOutput};
+pub use im::{Actor, OutputKind, PlainActor, PlainInput, PlainOutput};
 
 /// Actor client state update interface
 pub trait Update {
     fn update(&mut self) {}
+    /// Receives the simulation clock before each [update](Update::update) if the model has a clock (see [Model::clock](crate::model::Model::clock))
+    fn tick(&mut self, _clock: &SimClock) {}
+    /// Receives the seed of the client random number generators if the model is seeded (see [Model::seed](crate::model::Model::seed))
+    fn seed(&mut self, _seed: u64) {}
+    /// Returns `true` once the client is done, the actor loop then ends after the client [update](Update::update)
+    fn finished(&self) -> bool {
+        false
+    }
+    /// Returns the description of the client failure, if any, the actor loop then ends with the error [ActorError::Client](crate::ActorError::Client) after the client [update](Update::update)
+    fn failure(&self) -> Option<String> {
+        None
+    }
+}
+
+/**
+Simulation clock
+
+The clock is shared by all the actors of a [Model](crate::model::Model):
+the step of the clock is the index of the simulation sample at the sampling frequency of the model.
+An actor `Actor<C, NI, NO>` updates its client every `NI` samples, or every `NO` samples for an [Initiator].
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimClock {
+    step: usize,
+    sampling_frequency_hz: f64,
+}
+impl SimClock {
+    pub(crate) fn new(step: usize, sampling_frequency_hz: f64) -> Self {
+        Self {
+            step,
+            sampling_frequency_hz,
+        }
+    }
+    /// Returns the simulation step
+    pub fn step(&self) -> usize {
+        self.step
+    }
+    /// Returns the simulation sampling frequency
+    pub fn sampling_frequency_hz(&self) -> f64 {
+        self.sampling_frequency_hz
+    }
+    /// Returns the simulation time in seconds
+    pub fn time(&self) -> f64 {
+        self.step as f64 / self.sampling_frequency_hz
+    }
+}
+
+/// Upper bounds of the buckets of the loop times histograms, the last bucket is unbounded
+pub const LOOP_TIME_BUCKETS: [Duration; 6] = [
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+];
+
+/// Actor loop progress
+///
+/// Records the number of client updates and the time of the last update,
+/// and optionally the histogram of the time in-between updates
+#[derive(Debug, Default)]
+pub struct Progress {
+    iterations: AtomicUsize,
+    last: Mutex<Option<Instant>>,
+    loop_times: OnceLock<[AtomicUsize; LOOP_TIME_BUCKETS.len() + 1]>,
+}
+impl Progress {
+    // Records a client update
+    pub(crate) fn tick(&self) {
+        self.iterations.fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+        let mut last = self.last.lock().unwrap();
+        if let (Some(histogram), Some(last)) = (self.loop_times.get(), *last) {
+            let elapsed = now.duration_since(last);
+            let k = LOOP_TIME_BUCKETS.partition_point(|&bound| bound <= elapsed);
+            histogram[k].fetch_add(1, Ordering::Relaxed);
+        }
+        *last = Some(now);
+    }
+    // Starts recording the histogram of the loop times
+    pub(crate) fn record_loop_times(&self) {
+        let _ = self.loop_times.set(Default::default());
+    }
+    /// Returns the histogram of the time in-between client updates, [None] if the loop times are not recorded
+    ///
+    /// The bucket #`k` counts the loop times less than [LOOP_TIME_BUCKETS]`[k]`
+    /// and greater or equal to the previous bound, the last bucket counts the loop times above 1s
+    pub fn loop_times(&self) -> Option<Vec<usize>> {
+        self.loop_times.get().map(|histogram| {
+            histogram
+                .iter()
+                .map(|count| count.load(Ordering::Relaxed))
+                .collect()
+        })
+    }
+    /// Returns the number of client updates
+    pub fn iterations(&self) -> usize {
+        self.iterations.load(Ordering::Relaxed)
+    }
+    /// Returns the time elapsed since the last client update, [None] if the client has never been updated
+    pub fn idle(&self) -> Option<Duration> {
+        self.last.lock().unwrap().map(|last| last.elapsed())
+    }
+}
+
+/// Pause gate of the actor loops
+///
+/// The gate is shared by all the actors of a running [Model](crate::model::Model),
+/// it is checked at the top of each loop iteration and the actors wait at the gate while the model is paused
+#[derive(Debug, Default)]
+pub struct Gate {
+    paused: AtomicBool,
+    notify: tokio::sync::Notify,
+}
+impl Gate {
+    // Closes the gate
+    pub(crate) fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+    // Opens the gate and wakes up the waiting actors
+    pub(crate) fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+    /// Returns `true` if the gate is closed
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+    // Waits until the gate is open
+    pub(crate) async fn wait(&self) {
+        loop {
+            // the waiter is registered before checking the gate so a resume cannot be missed
+            let resumed = self.notify.notified();
+            if !self.is_paused() {
+                return;
+            }
+            resumed.await;
+        }
+    }
+}
+
+/// Non-finite value caught by the NaN guard of an actor (see [Model::nan_guard](crate::model::Model::nan_guard))
+#[derive(Debug, Clone)]
+pub struct NonFinite {
+    /// Actor name
+    pub actor: String,
+    /// Output type name
+    pub output: String,
+    /// Index of the client update that produced the value
+    pub step: usize,
 }
 
 /// Type alias for an actor without outputs
@@ -78,5 +237,57 @@ pub trait Task: Send {
     fn check_outputs(&self) -> Result<()>;
     /// Run the actor loop
     async fn task(&mut self);
+    /**
+    Ends the actor loop with the loop `result`
+
+    The error, if any, is printed and a non-finite value error is also reported to the model [nan_guard](crate::model::Model::nan_guard),
+    then the model is notified of the actor completion
+    */
+    fn complete(&mut self, result: Result<()>);
+    /// Sends the bootstrapped outputs
+    async fn init(&mut self) -> Result<()>;
+    /// Runs a single iteration of the actor loop
+    ///
+    /// Returns `true` if the client is [finished](Update::finished)
+    async fn step(&mut self) -> Result<bool>;
     fn as_plain(&self) -> PlainActor;
+    /**
+    Resizes the bounded channels of the outputs to the outputs burst size
+
+    If `ticks` is given, the channels are resized to hold the samples sent within `ticks` ticks
+    in addition to a bootstrapped sample.
+
+    Returns the receivers of the new channels with the identifiers of the channels they replace
+    */
+    fn resize_outputs(&mut self, ticks: Option<usize>) -> Vec<(usize, Box<dyn Any + Send>)>;
+    /**
+    Replaces the receiver of the input connected to the channel `id`
+
+    Returns `rx` if none of the inputs is connected to the channel
+    */
+    fn rewire_input(&mut self, id: usize, rx: Box<dyn Any + Send>) -> Option<Box<dyn Any + Send>>;
+    /**
+    Replaces the receiver of the first input which channel has lost all its senders with the receiver `rx` of the channel `id`
+
+    Returns `rx` if none of the inputs is dangling or if the data types do not match
+    */
+    fn rewire_dangling_input(
+        &mut self,
+        id: usize,
+        rx: Box<dyn Any + Send>,
+    ) -> Option<Box<dyn Any + Send>>;
+    /// Removes the outputs channels which receivers have been dropped
+    fn prune_outputs(&mut self);
+    /// Returns the actor loop progress
+    fn progress(&self) -> Arc<Progress>;
+    /// Returns the counter of the loop iterations of an [Initiator], [None] for the other actors
+    fn steps(&self) -> Option<Arc<AtomicUsize>>;
+    /// Sets the sampling frequency of the simulation clock
+    fn clock(&mut self, sampling_frequency_hz: f64);
+    /// Sets the seed of the client random number generators, the client is seeded before the outputs are bootstrapped
+    fn seed(&mut self, seed: u64);
+    /// Sets the pause gate checked at the top of the actor loop
+    fn gate(&mut self, gate: Arc<Gate>);
+    /// Checks the outputs for non-finite values, the first one ends the actor loop and is reported to `tx`
+    fn nan_guard(&mut self, tx: flume::Sender<NonFinite>);
 }
@@ -39,20 +39,73 @@ let logging = Arrow::builder(1000)
                        .build();
 ```
 
+Summary statistics (mean, standard deviation, minimum and maximum) of each entry
+over windows of consecutive samples are also saved into a separate Parquet file (`data_summary.parquet`) with
+```no_run
+# use dos_actors::clients::arrow_client::Arrow;
+# use dos_actors::prelude::*;
+# enum MyData {};
+let logging = Arrow::builder(1000)
+                       .entry::<f64,MyData>(42)
+                       .summary(100)
+                       .build();
+```
+
+Entries logged at a lower rate than the simulation sampling rate are declared with their rate,
+each entry is then associated with a `<entry>_step` column with the simulation step index of each sample (see [Arrow::record])
+```no_run
+# use dos_actors::clients::arrow_client::Arrow;
+# use dos_actors::prelude::*;
+# enum MyData {};
+# enum MySlowData {};
+let logging = Arrow::builder(1000)
+                       .entry::<f64,MyData>(42)
+                       .entry_with_rate::<f64,MySlowData>(42, 10)
+                       .build();
+```
+
+For long simulations, the data can be [streamed](ArrowBuilder::streaming) to the Parquet file by row groups
+of a given number of samples, bounding the memory used by the logger
+```no_run
+# use dos_actors::clients::arrow_client::Arrow;
+# use dos_actors::prelude::*;
+# enum MyData {};
+let logging = Arrow::builder(1_000_000)
+                       .entry::<f64,MyData>(42)
+                       .streaming(10_000)
+                       .build();
+```
+
+A [TeeLogger] duplicates the data into both an in-memory [Logging](crate::clients::Logging) and an [Arrow] logger.
+
 */
 
 use crate::{
+    clients::Logging,
     io::{Data, Read},
     Update, Who,
 };
 use arrow::{
-    array::{Array, ArrayData, BufferBuilder, Float64Array, ListArray},
+    array::{
+        Array, ArrayData, BufferBuilder, ListArray, ListBuilder, PrimitiveArray, PrimitiveBuilder,
+        UInt64Array, UInt64Builder,
+    },
     buffer::Buffer,
-    datatypes::{ArrowNativeType, DataType, Field, Schema, ToByteSlice},
+    datatypes::{
+        ArrowNativeType, ArrowPrimitiveType, DataType, Field, Float32Type, Float64Type, Schema,
+        ToByteSlice,
+    },
     record_batch::RecordBatch,
 };
 use parquet::{arrow::arrow_writer::ArrowWriter, file::properties::WriterProperties};
-use std::{any::Any, collections::HashMap, fmt::Display, fs::File, path::Path, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    path::Path,
+    sync::Arc,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ArrowError {
@@ -68,6 +121,14 @@ pub enum ArrowError {
     FieldNotFound(String),
     #[error("Parsing field {0} failed")]
     ParseField(String),
+    #[error("no summary window set")]
+    NoSummary,
+    #[error("there are no entries in the Arrow data logger")]
+    NoEntry,
+    #[error("entry {0} has a size of 0")]
+    ZeroSize(String),
+    #[error("the record of a streaming logger is not available")]
+    Streaming,
 }
 
 type Result<T> = std::result::Result<T, ArrowError>;
@@ -76,7 +137,10 @@ trait BufferObject: Send + Sync {
     fn who(&self) -> String;
     fn as_any(&self) -> &dyn Any;
     fn as_mut_any(&mut self) -> &mut dyn Any;
+    fn len(&self) -> usize;
     fn into_list(&mut self, n_step: usize, n: usize) -> Result<ListArray>;
+    /// Replaces the buffer with an empty buffer of `capacity`
+    fn reset(&mut self, capacity: usize);
 }
 
 impl<T: ArrowNativeType, U: 'static + Send + Sync> BufferObject for Data<BufferBuilder<T>, U> {
@@ -89,24 +153,101 @@ impl<T: ArrowNativeType, U: 'static + Send + Sync> BufferObject for Data<BufferB
     fn as_mut_any(&mut self) -> &mut dyn Any {
         self
     }
+    fn len(&self) -> usize {
+        BufferBuilder::<T>::len(&**self)
+    }
     fn into_list(&mut self, n_step: usize, n: usize) -> Result<ListArray> {
         let buffer = &mut *self;
-        let data = ArrayData::builder(DataType::Float64)
+        let data = ArrayData::builder(data_type::<T>())
             .len(buffer.len())
             .add_buffer(buffer.finish())
             .build()?;
-        let offsets = (0..).step_by(n).take(n_step + 1).collect::<Vec<i32>>();
-        let list = ArrayData::builder(DataType::List(Box::new(Field::new(
-            "values",
-            DataType::Float64,
-            false,
-        ))))
-        .len(n_step)
-        .add_buffer(Buffer::from(&offsets.to_byte_slice()))
-        .add_child_data(data)
-        .build()?;
-        Ok(ListArray::from(list))
+        list_from(data, n_step, n)
+    }
+    fn reset(&mut self, capacity: usize) {
+        **self = BufferBuilder::<T>::new(capacity);
+    }
+}
+
+// Arrow data type of the native type `T`, `f32` is stored as [DataType::Float32] and any other type as [DataType::Float64]
+fn data_type<T: ArrowNativeType>() -> DataType {
+    if TypeId::of::<T>() == TypeId::of::<f32>() {
+        DataType::Float32
+    } else {
+        DataType::Float64
+    }
+}
+
+// Builds a list array of `n_step` items of size `n` from the array `data`
+fn list_from(data: ArrayData, n_step: usize, n: usize) -> Result<ListArray> {
+    let offsets = (0..=n_step).map(|i| (i * n) as i32).collect::<Vec<i32>>();
+    let list = ArrayData::builder(DataType::List(Box::new(Field::new(
+        "values",
+        data.data_type().clone(),
+        false,
+    ))))
+    .len(n_step)
+    .add_buffer(Buffer::from(&offsets.to_byte_slice()))
+    .add_child_data(data)
+    .build()?;
+    Ok(ListArray::from(list))
+}
+
+// Returns the non-null items of a list array column of primitive type `P`
+fn primitive_list_values<P: ArrowPrimitiveType>(column: &dyn Array) -> Option<Vec<Vec<P::Native>>> {
+    column
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .and_then(|data| {
+            data.iter()
+                .flatten()
+                .map(|data| {
+                    data.as_any()
+                        .downcast_ref::<PrimitiveArray<P>>()
+                        .and_then(|data| data.iter().collect::<Option<Vec<P::Native>>>())
+                })
+                .collect::<Option<Vec<Vec<P::Native>>>>()
+        })
+}
+// Returns the non-null items of a list array column, `f32` items are promoted to `f64`
+fn list_values(column: &dyn Array) -> Option<Vec<Vec<f64>>> {
+    primitive_list_values::<Float64Type>(column).or_else(|| {
+        primitive_list_values::<Float32Type>(column).map(|values| {
+            values
+                .into_iter()
+                .map(|value| value.into_iter().map(f64::from).collect())
+                .collect()
+        })
+    })
+}
+// Spreads the samples of an entry of size `n` over the rows of the simulation steps `first_step..first_step+n_row`,
+// each sample is written in the row of the simulation step `steps` it has been received at
+//
+// Returns the list of samples with null rows in between and the simulation step index of each sample
+fn sparse_list<P: ArrowPrimitiveType>(
+    samples: impl Iterator<Item = Vec<P::Native>>,
+    steps: &[usize],
+    first_step: usize,
+    n_row: usize,
+    n: usize,
+) -> Result<(ListArray, UInt64Array)> {
+    let mut values = ListBuilder::new(PrimitiveBuilder::<P>::new(steps.len() * n));
+    let mut sample_steps = UInt64Builder::new(n_row);
+    let mut samples = samples.zip(steps.iter().copied()).peekable();
+    for row in first_step..first_step + n_row {
+        match samples.next_if(|(_, step)| *step <= row) {
+            Some((sample, step)) => {
+                values.values().append_slice(&sample)?;
+                values.append(true)?;
+                sample_steps.append_value(step as u64)?;
+            }
+            None => {
+                values.append(false)?;
+                sample_steps.append_null()?;
+            }
+        }
     }
+    Ok((values.finish(), sample_steps.finish()))
 }
 
 /// Arrow format logger builder
@@ -117,6 +258,10 @@ pub struct ArrowBuilder {
     metadata: Option<HashMap<String, String>>,
     n_entry: usize,
     drop_option: DropOption,
+    summary: Option<usize>,
+    rates: Vec<usize>,
+    warmup: usize,
+    row_group_size: Option<usize>,
 }
 impl ArrowBuilder {
     /// Creates a new Arrow logger builder
@@ -128,23 +273,144 @@ impl ArrowBuilder {
             metadata: None,
             n_entry: 0,
             drop_option: DropOption::Save(None),
+            summary: None,
+            rates: Vec::new(),
+            warmup: 0,
+            row_group_size: None,
         }
     }
-    /// Adds an entry to the logger
+    /**
+    Adds an entry to the logger
+
+    The entry is stored with the precision of `T`:
+    an `f32` entry halves the size of the data and the `Vec<f64>` inputs are converted to `f32` when written to the entry.
+    The `f32` entries are read back as `f32` with [Arrow::get_f32] or promoted to `f64` with [Arrow::get].
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::clients::arrow_client::Arrow;
+    use dos_actors::prelude::*;
+    use parquet::{
+        basic::Type,
+        file::reader::{FileReader, SerializedFileReader},
+    };
+    enum Sig {};
+    let n_step = 100;
+    let path = std::env::temp_dir().join("arrow_f32.parquet");
+    let mut source: Initiator<_> = Signals::new(42, n_step).signals(Signal::Ramp { a: 0.5, b: 0. }).into();
+    let logging = Arrow::builder(n_step)
+        .entry::<f32, Sig>(42)
+        .filename(path.to_str().unwrap())
+        .build()
+        .into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    {
+        let mut arrow = logging.lock().await;
+        assert_eq!(arrow.get_f32("Sig")?[10], vec![5f32; 42]);
+        assert_eq!(arrow.get("Sig")?[10], vec![5f64; 42]);
+    }
+    drop(logging);
+    let reader = SerializedFileReader::new(std::fs::File::open(&path)?)?;
+    let schema = reader.metadata().file_metadata().schema_descr();
+    assert_eq!(schema.column(0).physical_type(), Type::FLOAT);
+    # Ok::<(), anyhow::Error>(())
+    # }).unwrap();
+    ```
+    */
     pub fn entry<T, U>(self, size: usize) -> Self
     where
         T: 'static + ArrowNativeType + Send + Sync,
         U: 'static + Send + Sync,
     {
+        self.entry_with_rate::<T, U>(size, 1)
+    }
+    /**
+    Adds an entry to the logger that is written every `rate` simulation steps
+
+    # Example
+    A 1kHz and a 200Hz entries logged into the same record after a 10 steps warm-up
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::clients::arrow_client::Arrow;
+    use dos_actors::prelude::*;
+    use arrow::array::UInt64Array;
+    enum Fast {};
+    enum Slow {};
+    let n_step = 1000;
+    let mut fast_source: Initiator<_> = Signals::new(1, n_step).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let mut slow_source: Initiator<_> = Signals::new(1, n_step).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let mut sampler: Actor<_, 1, 5> = Sampler::<Vec<f64>, Slow>::default().into();
+    let logging = Arrow::builder(n_step)
+        .entry::<f64, Fast>(1)
+        .entry_with_rate::<f64, Slow>(1, 5)
+        .warmup(10)
+        .no_save()
+        .build()
+        .into_arcx();
+    let mut fast_sink = Terminator::<_>::new(logging.clone());
+    let mut slow_sink = Terminator::<_, 5>::new(logging.clone());
+    fast_source.add_output().build::<Vec<f64>, Fast>().into_input(&mut fast_sink);
+    slow_source.add_output().build::<Vec<f64>, Slow>().into_input(&mut sampler);
+    sampler.add_output().build::<Vec<f64>, Slow>().into_input(&mut slow_sink);
+    Model::new(vec![
+        Box::new(fast_source),
+        Box::new(slow_source),
+        Box::new(sampler),
+        Box::new(fast_sink),
+        Box::new(slow_sink),
+    ])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+    let mut arrow = logging.lock().await;
+    let record = arrow.record()?;
+    assert_eq!(record.num_rows(), n_step - 10);
+    let steps = |name: &str| -> Vec<u64> {
+        let (idx, _) = record.schema().column_with_name(name).unwrap();
+        record
+            .column(idx)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap()
+            .iter()
+            .flatten()
+            .collect()
+    };
+    let (fast, slow) = (steps("Fast_step"), steps("Slow_step"));
+    assert_eq!((fast[0], slow[0]), (10, 10));
+    assert_eq!(fast.len(), 5 * slow.len());
+    assert!(fast.windows(2).all(|s| s[1] - s[0] == 1));
+    assert!(slow.windows(2).all(|s| s[1] - s[0] == 5));
+    # Ok::<(), anyhow::Error>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn entry_with_rate<T, U>(self, size: usize, rate: usize) -> Self
+    where
+        T: 'static + ArrowNativeType + Send + Sync,
+        U: 'static + Send + Sync,
+    {
+        assert!(rate > 0, "the entry rate must be greater than 0");
         let mut buffers = self.buffers;
         let buffer: Data<BufferBuilder<T>, U> =
-            Data::new(BufferBuilder::<T>::new(size * self.n_step));
+            Data::new(BufferBuilder::<T>::new(size * self.n_step / rate));
         buffers.push(Box::new(buffer));
         let mut capacities = self.capacities;
         capacities.push(size);
+        let mut rates = self.rates;
+        rates.push(rate);
         Self {
             buffers,
             capacities,
+            rates,
             n_entry: self.n_entry + 1,
             ..self
         }
@@ -156,6 +422,109 @@ impl ArrowBuilder {
             ..self
         }
     }
+    /// Computes the summary statistics of the entries over `window` samples
+    ///
+    /// The summary is saved into a separate file with the same name as the data file
+    /// appended with `_summary`
+    pub fn summary(self, window: usize) -> Self {
+        assert!(window > 0, "the summary window must be greater than 0");
+        Self {
+            summary: Some(window),
+            ..self
+        }
+    }
+    /**
+    Discards the first `n_step` samples of each entry
+
+    For an entry with a rate `r`, the first `n_step/r` samples are discarded.
+    The number of steps given to the [builder](Arrow::builder) must include the warm-up steps.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::clients::arrow_client::Arrow;
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let n_step = 400;
+    let mut source: Initiator<_> = Signals::new(1, n_step).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let logging = Arrow::builder(n_step)
+        .entry::<f64, Sig>(1)
+        .warmup(100)
+        .no_save()
+        .build()
+        .into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let mut logging = logging.lock().await;
+    assert_eq!(logging.record()?.num_rows(), 300);
+    assert_eq!(logging.get("Sig")?[0], vec![100f64]);
+    # Ok::<(), anyhow::Error>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn warmup(self, n_step: usize) -> Self {
+        Self {
+            warmup: n_step,
+            ..self
+        }
+    }
+    /**
+    Streams the data to the Parquet file by row groups of `row_group_size` samples
+
+    A row group is written as soon as all the entries have received `row_group_size` samples,
+    the remaining samples are written when the logger is dropped.
+    The data is not kept in memory: [Arrow::record] and [Arrow::get] return [ArrowError::Streaming].
+    A streaming logger cannot have entries at lower rates nor a [summary](ArrowBuilder::summary).
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::clients::arrow_client::{Arrow, ArrowError};
+    use dos_actors::prelude::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    enum Sig {};
+    let n_step = 100;
+    let path = std::env::temp_dir().join("arrow_streaming.parquet");
+    let mut source: Initiator<_> = Signals::new(3, n_step).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let logging = Arrow::builder(n_step)
+        .entry::<f64, Sig>(3)
+        .filename(path.to_str().unwrap())
+        .streaming(30)
+        .build()
+        .into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert!(matches!(logging.lock().await.get("Sig"), Err(ArrowError::Streaming)));
+    drop(logging);
+    let reader = SerializedFileReader::new(std::fs::File::open(&path)?)?;
+    let metadata = reader.metadata();
+    assert_eq!(metadata.num_row_groups(), 4);
+    assert_eq!(metadata.row_group(3).num_rows(), 10);
+    assert_eq!(metadata.file_metadata().num_rows() as usize, n_step);
+    # Ok::<(), anyhow::Error>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn streaming(self, row_group_size: usize) -> Self {
+        assert!(
+            row_group_size > 0,
+            "the row group size must be greater than 0"
+        );
+        Self {
+            row_group_size: Some(row_group_size),
+            ..self
+        }
+    }
     /// No saving to parquet file
     pub fn no_save(self) -> Self {
         Self {
@@ -163,21 +532,69 @@ impl ArrowBuilder {
             ..self
         }
     }
-    /// Builds the Arrow logger
-    pub fn build(self) -> Arrow {
+    /**
+    Builds the Arrow logger
+
+    Returns an error if there are no entries or if the size of an entry is 0
+
+    # Example
+    ```
+    use dos_actors::clients::arrow_client::{Arrow, ArrowError};
+    enum MyData {};
+    let logging = Arrow::builder(1000).entry::<f64, MyData>(0).no_save().try_build();
+    assert!(matches!(logging, Err(ArrowError::ZeroSize(_))));
+    ```
+    */
+    pub fn try_build(self) -> Result<Arrow> {
         if self.n_entry == 0 {
-            panic!("There are no entries in the Arrow data logger.");
+            return Err(ArrowError::NoEntry);
+        }
+        if let Some((buffer, _)) = self
+            .buffers
+            .iter()
+            .zip(self.capacities.iter())
+            .find(|(_, n)| **n == 0)
+        {
+            return Err(ArrowError::ZeroSize(buffer.who()));
+        }
+        let mut buffers = self.buffers;
+        if let Some(row_group_size) = self.row_group_size {
+            assert!(
+                matches!(self.drop_option, DropOption::Save(_)),
+                "a streaming Arrow logger must save the data"
+            );
+            assert!(
+                self.summary.is_none() && self.rates.iter().all(|rate| *rate == 1),
+                "a streaming Arrow logger cannot have a summary nor entries at lower rates"
+            );
+            // the buffers only hold a row group
+            for (buffer, n) in buffers.iter_mut().zip(self.capacities.iter()) {
+                buffer.reset(row_group_size * n);
+            }
         }
-        Arrow {
+        Ok(Arrow {
             n_step: self.n_step,
             capacities: self.capacities,
-            buffers: self.buffers,
+            buffers,
             metadata: self.metadata,
             step: 0,
             n_entry: self.n_entry,
             record: None,
             drop_option: self.drop_option,
-        }
+            summary: self.summary,
+            warmup: self.rates.iter().map(|rate| self.warmup / rate).collect(),
+            n_read: vec![0; self.n_entry],
+            steps: vec![Vec::new(); self.n_entry],
+            rates: self.rates,
+            row_group_size: self.row_group_size,
+            writer: None,
+        })
+    }
+    /// Builds the Arrow logger
+    ///
+    /// Panics if there are no entries or if the size of an entry is 0, see [ArrowBuilder::try_build]
+    pub fn build(self) -> Arrow {
+        self.try_build().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -196,6 +613,13 @@ pub struct Arrow {
     n_entry: usize,
     record: Option<RecordBatch>,
     drop_option: DropOption,
+    summary: Option<usize>,
+    rates: Vec<usize>,
+    warmup: Vec<usize>,
+    n_read: Vec<usize>,
+    steps: Vec<Vec<usize>>,
+    row_group_size: Option<usize>,
+    writer: Option<ArrowWriter<File>>,
 }
 impl Arrow {
     /// Creates a new Apache [Arrow](https://docs.rs/arrow) data logger
@@ -204,18 +628,94 @@ impl Arrow {
     pub fn builder(n_step: usize) -> ArrowBuilder {
         ArrowBuilder::new(n_step)
     }
-    fn data<T, U>(&mut self) -> Option<&mut Data<BufferBuilder<T>, U>>
+    // Returns the index of the buffer of the entry `U` stored as `T`
+    fn position<T, U>(&self) -> Option<usize>
     where
         T: 'static + ArrowNativeType,
         U: 'static,
     {
         self.buffers
-            .iter_mut()
-            .find_map(|b| b.as_mut_any().downcast_mut::<Data<BufferBuilder<T>, U>>())
+            .iter()
+            .position(|b| b.as_any().is::<Data<BufferBuilder<T>, U>>())
     }
     pub fn pct_complete(&self) -> usize {
         self.step / self.n_step / self.n_entry
     }
+    // Returns the name of the Parquet file
+    fn file_name(&self) -> String {
+        match self.drop_option {
+            DropOption::Save(Some(ref filename)) => filename.clone(),
+            _ => "data.parquet".to_string(),
+        }
+    }
+    // Builds a record from the samples in the buffers, emptying the buffers
+    fn take_record(&mut self) -> Result<RecordBatch> {
+        let mut lists: Vec<Arc<dyn Array>> = vec![];
+        let mut fields = vec![];
+        for (buffer, n) in self.buffers.iter_mut().zip(self.capacities.iter()) {
+            let n_step = buffer.len() / n;
+            let list = buffer.into_list(n_step, *n)?;
+            fields.push(Field::new(
+                buffer.who().split("::").last().unwrap_or("no name"),
+                DataType::List(Box::new(Field::new("values", list.value_type(), false))),
+                false,
+            ));
+            lists.push(Arc::new(list));
+        }
+
+        let schema = Arc::new(if let Some(metadata) = self.metadata.as_ref() {
+            Schema::new_with_metadata(fields, metadata.clone())
+        } else {
+            Schema::new(fields)
+        });
+
+        Ok(RecordBatch::try_new(Arc::clone(&schema), lists)?)
+    }
+    // Checks if all the entries have received a complete row group
+    fn is_row_group_complete(&self, row_group_size: usize) -> bool {
+        let mut rows = self
+            .buffers
+            .iter()
+            .zip(self.capacities.iter())
+            .map(|(buffer, n)| buffer.len() / n);
+        let first = rows.next().unwrap_or_default();
+        first >= row_group_size && rows.all(|row| row == first)
+    }
+    // Writes the samples in the buffers as a new row group of the Parquet file
+    fn write_row_group(&mut self, row_group_size: usize) -> Result<()> {
+        let batch = self.take_record()?;
+        for (buffer, n) in self.buffers.iter_mut().zip(self.capacities.iter()) {
+            buffer.reset(row_group_size * n);
+        }
+        self.steps.iter_mut().for_each(|steps| steps.clear());
+        self.write_batch(&batch, row_group_size)
+    }
+    // Writes a record into the Parquet file, creating the file if needed
+    fn write_batch(&mut self, batch: &RecordBatch, row_group_size: usize) -> Result<()> {
+        if self.writer.is_none() {
+            let file = File::create(self.file_name())?;
+            let props = WriterProperties::builder()
+                .set_max_row_group_size(row_group_size)
+                .build();
+            self.writer = Some(ArrowWriter::try_new(file, batch.schema(), Some(props))?);
+        }
+        if let Some(writer) = self.writer.as_mut() {
+            writer.write(batch)?;
+        }
+        Ok(())
+    }
+    // Writes the remaining samples and closes the Parquet file
+    fn close(&mut self, row_group_size: usize) -> Result<()> {
+        let batch = self.take_record()?;
+        if self.writer.is_none() || batch.num_rows() > 0 {
+            self.write_batch(&batch, row_group_size)?;
+        }
+        if let Some(mut writer) = self.writer.take() {
+            writer.close()?;
+            println!("Data saved to {:?}", self.file_name());
+        }
+        Ok(())
+    }
 }
 
 impl Display for Arrow {
@@ -239,15 +739,33 @@ impl Drop for Arrow {
     fn drop(&mut self) {
         println!("{self}");
         match self.drop_option {
-            DropOption::Save(ref filename) => {
-                let file_name = filename
-                    .as_ref()
-                    .cloned()
-                    .unwrap_or("data.parquet".to_string());
-                if let Err(e) = self.to_parquet(file_name) {
+            DropOption::Save(_) if self.row_group_size.is_some() => {
+                let row_group_size = self.row_group_size.unwrap_or_default();
+                if let Err(e) = self.close(row_group_size) {
                     println!("{e}");
                 }
             }
+            DropOption::Save(_) => {
+                let file_name = self.file_name();
+                if let Err(e) = self.to_parquet(&file_name) {
+                    println!("{e}");
+                }
+                if self.summary.is_some() {
+                    let path = Path::new(&file_name);
+                    let summary_file_name = path.with_file_name(format!(
+                        "{}_summary.parquet",
+                        path.file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .unwrap_or("data")
+                    ));
+                    if let Err(e) = self
+                        .summary_record()
+                        .and_then(|summary| write_parquet(&summary, summary_file_name))
+                    {
+                        println!("{e}");
+                    }
+                }
+            }
             DropOption::NoSave => {
                 println!("Dropping Arrow logger without saving.");
             }
@@ -256,48 +774,176 @@ impl Drop for Arrow {
 }
 impl Arrow {
     /// Returns the data record
+    ///
+    /// If some entries are logged at different rates, the record has as many rows as simulation steps,
+    /// the rows without samples are null and each entry `<entry>` is followed by a `<entry>_step` column
+    /// with the simulation step index of each sample.
+    /// The step of a sample is the step the data is [stamped](crate::io::Data::stamped) with or, if the data is not stamped,
+    /// the number of samples received by the entry before it, warm-up included, times the entry rate.
+    ///
+    /// The record of a [streaming](ArrowBuilder::streaming) logger is not available, [ArrowError::Streaming] is returned instead.
     pub fn record(&mut self) -> Result<&RecordBatch> {
-        if self.record.is_none() {
+        if self.row_group_size.is_some() {
+            return Err(ArrowError::Streaming);
+        }
+        if self.record.is_none() && self.rates.iter().any(|rate| *rate > 1) {
+            let first_step = self
+                .steps
+                .iter()
+                .filter_map(|steps| steps.first())
+                .min()
+                .copied()
+                .unwrap_or_default();
+            let n_row = self
+                .steps
+                .iter()
+                .filter_map(|steps| steps.last())
+                .max()
+                .map_or(0, |last_step| last_step + 1 - first_step);
             let mut lists: Vec<Arc<dyn Array>> = vec![];
-            for (buffer, n) in self.buffers.iter_mut().zip(self.capacities.iter()) {
-                let list = buffer.into_list(self.step / self.n_entry, *n)?;
-                lists.push(Arc::new(list));
-            }
-
-            let fields: Vec<_> = self
+            let mut fields = vec![];
+            for ((buffer, n), steps) in self
                 .buffers
-                .iter()
-                .map(|buffer| {
-                    Field::new(
-                        &buffer.who().split("::").last().unwrap_or("no name"),
-                        DataType::List(Box::new(Field::new("values", DataType::Float64, false))),
-                        false,
-                    )
-                })
-                .collect();
+                .iter_mut()
+                .zip(self.capacities.iter())
+                .zip(self.steps.iter_mut())
+            {
+                let steps = std::mem::take(steps);
+                let name = buffer
+                    .who()
+                    .split("::")
+                    .last()
+                    .unwrap_or("no name")
+                    .to_string();
+                let n_sample = buffer.len() / n;
+                let list = buffer.into_list(n_sample, *n)?;
+                let value_type = list.value_type();
+                let (values, steps) = match value_type {
+                    DataType::Float32 => sparse_list::<Float32Type>(
+                        primitive_list_values::<Float32Type>(&list)
+                            .ok_or_else(|| ArrowError::ParseField(name.clone()))?
+                            .into_iter(),
+                        &steps,
+                        first_step,
+                        n_row,
+                        *n,
+                    )?,
+                    _ => sparse_list::<Float64Type>(
+                        primitive_list_values::<Float64Type>(&list)
+                            .ok_or_else(|| ArrowError::ParseField(name.clone()))?
+                            .into_iter(),
+                        &steps,
+                        first_step,
+                        n_row,
+                        *n,
+                    )?,
+                };
+                lists.push(Arc::new(values));
+                lists.push(Arc::new(steps));
+                fields.push(Field::new(
+                    &name,
+                    DataType::List(Box::new(Field::new("item", value_type, true))),
+                    true,
+                ));
+                fields.push(Field::new(&format!("{name}_step"), DataType::UInt64, true));
+            }
             let schema = Arc::new(if let Some(metadata) = self.metadata.as_ref() {
                 Schema::new_with_metadata(fields, metadata.clone())
             } else {
                 Schema::new(fields)
             });
-
             self.record = Some(RecordBatch::try_new(Arc::clone(&schema), lists)?);
         }
+        if self.record.is_none() {
+            self.record = Some(self.take_record()?);
+        }
         self.record.as_ref().ok_or(ArrowError::NoRecord)
     }
+    /**
+    Returns the summary record
+
+    The summary record has one row per window of samples and the mean, standard deviation,
+    minimum and maximum of each entry element in each window.
+    If the number of samples is not a multiple of the window, the last row summarizes the remaining samples.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::clients::arrow_client::Arrow;
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let (n_step, window) = (105, 10);
+    let mut source: Initiator<_> = Signals::new(2, n_step).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let logging = Arrow::builder(n_step)
+        .entry::<f64, Sig>(2)
+        .summary(window)
+        .no_save()
+        .build()
+        .into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let summary = logging.lock().await.summary_record()?;
+    assert_eq!(summary.num_rows(), (n_step + window - 1) / window);
+    assert_eq!(summary.num_columns(), 4);
+    # Ok::<(), anyhow::Error>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn summary_record(&mut self) -> Result<RecordBatch> {
+        let window = self.summary.ok_or(ArrowError::NoSummary)?;
+        let record = self.record()?.clone();
+        let schema = record.schema();
+        let mut fields = vec![];
+        let mut lists: Vec<Arc<dyn Array>> = vec![];
+        for (field, column) in schema
+            .fields()
+            .iter()
+            .zip(record.columns())
+            .filter(|(field, _)| matches!(field.data_type(), DataType::List(_)))
+        {
+            let rows = list_values(column.as_ref())
+                .ok_or_else(|| ArrowError::ParseField(field.name().clone()))?;
+            let n = rows.first().map_or(0, |row| row.len());
+            let n_row = rows.chunks(window).len();
+            let mut stats: Vec<Vec<f64>> = (0..4).map(|_| Vec::with_capacity(n_row * n)).collect();
+            for chunk in rows.chunks(window) {
+                let m = chunk.len() as f64;
+                for i in 0..n {
+                    let values = chunk.iter().map(|row| row[i]);
+                    let mean = values.clone().sum::<f64>() / m;
+                    let var = values.clone().map(|x| (x - mean).powi(2)).sum::<f64>() / m;
+                    stats[0].push(mean);
+                    stats[1].push(var.sqrt());
+                    stats[2].push(values.clone().fold(f64::INFINITY, f64::min));
+                    stats[3].push(values.fold(f64::NEG_INFINITY, f64::max));
+                }
+            }
+            for (stat, values) in ["mean", "std", "min", "max"].into_iter().zip(stats) {
+                let data = ArrayData::builder(DataType::Float64)
+                    .len(values.len())
+                    .add_buffer(Buffer::from(values.to_byte_slice()))
+                    .build()?;
+                lists.push(Arc::new(list_from(data, n_row, n)?));
+                fields.push(Field::new(
+                    &format!("{}_{}", field.name(), stat),
+                    DataType::List(Box::new(Field::new("values", DataType::Float64, false))),
+                    false,
+                ));
+            }
+        }
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), lists)?)
+    }
     /// Saves the data to a [Parquet](https://docs.rs/parquet) data file
     pub fn to_parquet<P: AsRef<Path> + std::fmt::Debug>(&mut self, path: P) -> Result<()> {
         let batch = self.record()?;
-
-        let file = File::create(&path)?;
-        let props = WriterProperties::builder().build();
-        let mut writer = ArrowWriter::try_new(file, Arc::clone(&batch.schema()), Some(props))?;
-        writer.write(&batch)?;
-        writer.close()?;
-        println!("Data saved to {path:?}");
-        Ok(())
+        write_parquet(batch, path)
     }
-    /// Return the record field entry
+    /// Return the record field entry, `f32` entries are promoted to `f64`
     pub fn get<S>(&mut self, field_name: S) -> Result<Vec<Vec<f64>>>
     where
         S: AsRef<str>,
@@ -305,29 +951,39 @@ impl Arrow {
     {
         match self.record() {
             Ok(record) => match record.schema().column_with_name(field_name.as_ref()) {
-                Some((idx, _)) => record
-                    .column(idx)
-                    .as_any()
-                    .downcast_ref::<ListArray>()
-                    .map(|data| {
-                        data.iter()
-                            .map(|data| {
-                                data.map(|data| {
-                                    data.as_any()
-                                        .downcast_ref::<Float64Array>()
-                                        .and_then(|data| data.iter().collect::<Option<Vec<f64>>>())
-                                })
-                                .flatten()
-                            })
-                            .collect::<Option<Vec<Vec<f64>>>>()
-                    })
-                    .flatten()
+                Some((idx, _)) => list_values(record.column(idx).as_ref())
                     .ok_or(ArrowError::ParseField(field_name.into())),
                 None => Err(ArrowError::FieldNotFound(field_name.into())),
             },
             Err(e) => Err(e),
         }
     }
+    /// Return the record field entry of an `f32` entry
+    pub fn get_f32<S>(&mut self, field_name: S) -> Result<Vec<Vec<f32>>>
+    where
+        S: AsRef<str>,
+        String: From<S>,
+    {
+        match self.record() {
+            Ok(record) => match record.schema().column_with_name(field_name.as_ref()) {
+                Some((idx, _)) => primitive_list_values::<Float32Type>(record.column(idx).as_ref())
+                    .ok_or(ArrowError::ParseField(field_name.into())),
+                None => Err(ArrowError::FieldNotFound(field_name.into())),
+            },
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// Writes a record into a Parquet file
+fn write_parquet<P: AsRef<Path> + std::fmt::Debug>(batch: &RecordBatch, path: P) -> Result<()> {
+    let file = File::create(&path)?;
+    let props = WriterProperties::builder().build();
+    let mut writer = ArrowWriter::try_new(file, Arc::clone(&batch.schema()), Some(props))?;
+    writer.write(batch)?;
+    writer.close()?;
+    println!("Data saved to {path:?}");
+    Ok(())
 }
 
 impl Update for Arrow {}
@@ -342,10 +998,110 @@ where
             data.len(),
             data.iter().map(|x| x.len()).collect::<Vec<usize>>()
         );*/
+        let idx = self
+            .position::<T, U>()
+            .or_else(|| self.position::<f32, U>());
+        if let Some(idx) = idx {
+            let step = data.step().unwrap_or(self.n_read[idx] * self.rates[idx]);
+            self.n_read[idx] += 1;
+            if self.warmup[idx] > 0 {
+                self.warmup[idx] -= 1;
+                return;
+            }
+            self.steps[idx].push(step);
+        }
         self.step += 1;
-        if let Some(buffer_data) = self.data::<T, U>() {
-            let buffer = &mut *buffer_data;
-            buffer.append_slice((**data).as_slice());
+        if let Some(buffer) = idx.map(|idx| self.buffers[idx].as_mut_any()) {
+            if let Some(buffer) = buffer.downcast_mut::<Data<BufferBuilder<T>, U>>() {
+                buffer.append_slice((**data).as_slice());
+            } else if let (Some(buffer), Some(data)) = (
+                buffer.downcast_mut::<Data<BufferBuilder<f32>, U>>(),
+                (&**data as &dyn Any).downcast_ref::<Vec<f64>>(),
+            ) {
+                data.iter().for_each(|x| buffer.append(*x as f32));
+            }
+        }
+        if let Some(row_group_size) = self.row_group_size {
+            if self.is_row_group_complete(row_group_size) {
+                if let Err(e) = self.write_row_group(row_group_size) {
+                    crate::print_error("Arrow row group writing failed", &e);
+                }
+            }
         }
     }
 }
+
+/// Logger duplicating the data into both a [Logging] and an [Arrow] client
+///
+/// A single [Terminator](crate::Terminator) gives access to the data in memory with [TeeLogger::logging]
+/// and to the data [record](Arrow::record) that is saved into a [Parquet](https://docs.rs/parquet) file
+///
+/// # Example
+/// ```
+/// # tokio_test::block_on(async {
+/// use dos_actors::clients::arrow_client::{Arrow, TeeLogger};
+/// use dos_actors::prelude::*;
+/// use parquet::file::reader::{FileReader, SerializedFileReader};
+/// enum Sig {};
+/// let n_step = 100;
+/// let path = std::env::temp_dir().join("tee_logger.parquet");
+/// let mut source: Initiator<_> = Signals::new(1, n_step).signals(Signal::Ramp { a: 1., b: 0. }).into();
+/// let logging = TeeLogger::new(
+///     Logging::<f64>::default(),
+///     Arrow::builder(n_step)
+///         .entry::<f64, Sig>(1)
+///         .filename(path.to_str().unwrap())
+///         .build(),
+/// )
+/// .into_arcx();
+/// let mut sink = Terminator::<_>::new(logging.clone());
+/// source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+/// Model::new(vec![Box::new(source), Box::new(sink)])
+///     .check()?
+///     .run()
+///     .wait()
+///     .await?;
+/// {
+///     let mut tee = logging.lock().await;
+///     assert_eq!(tee.logging().len(), n_step);
+///     assert_eq!(tee.record()?.num_rows(), n_step);
+/// }
+/// drop(logging);
+/// let reader = SerializedFileReader::new(std::fs::File::open(&path)?)?;
+/// assert_eq!(reader.metadata().file_metadata().num_rows() as usize, n_step);
+/// # Ok::<(), anyhow::Error>(())
+/// # }).unwrap();
+/// ```
+pub struct TeeLogger<T> {
+    logging: Logging<T>,
+    arrow: Arrow,
+}
+impl<T> TeeLogger<T> {
+    /// Creates a new logger from a [Logging] and an [Arrow] client
+    pub fn new(logging: Logging<T>, arrow: Arrow) -> Self {
+        Self { logging, arrow }
+    }
+    /// Returns the in-memory logger
+    pub fn logging(&self) -> &Logging<T> {
+        &self.logging
+    }
+    /// Returns the Arrow logger
+    pub fn arrow(&mut self) -> &mut Arrow {
+        &mut self.arrow
+    }
+    /// Returns the Arrow data record
+    pub fn record(&mut self) -> Result<&RecordBatch> {
+        self.arrow.record()
+    }
+}
+impl<T> Update for TeeLogger<T> {}
+impl<T, U> Read<Vec<T>, U> for TeeLogger<T>
+where
+    T: ArrowNativeType + Clone,
+    U: 'static,
+{
+    fn read(&mut self, data: Arc<Data<Vec<T>, U>>) {
+        <Logging<T> as Read<Vec<T>, U>>::read(&mut self.logging, data.clone());
+        <Arrow as Read<Vec<T>, U>>::read(&mut self.arrow, data);
+    }
+}
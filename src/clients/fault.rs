@@ -0,0 +1,149 @@
+/*!
+# Fault injection
+
+The module implements a client wrapper that injects a fault into an actor at a given step,
+in order to test the behavior of a model when an actor fails.
+
+The module is enabled with the `fault-injection` feature.
+
+# Example
+The source outputs are dropped at step 50, stopping the model
+```
+# tokio_test::block_on(async {
+use dos_actors::{
+    clients::fault::{Fault, FaultInjector},
+    prelude::*,
+};
+enum Sig {};
+let mut source: Initiator<_> =
+    FaultInjector::new(Signals::new(1, 100), Fault::Disconnect, 50).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(logging.lock().await.len(), 50);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+
+use crate::{
+    io::{Data, Read, Write},
+    SimClock, Update,
+};
+use std::sync::Arc;
+
+/// Fault types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The client stops writing, the actor outputs are dropped
+    Disconnect,
+    /// The client is [finished](Update::finished), the actor loop ends
+    Stop,
+    /**
+    The client [fails](Update::failure), the actor loop ends with the error [ActorError::Client](crate::ActorError::Client)
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{
+        clients::fault::{Fault, FaultInjector},
+        model::ModelError,
+        prelude::*,
+        ActorError,
+    };
+    enum Sig {};
+    let mut source: Initiator<_> =
+        FaultInjector::new(Signals::new(1, 100), Fault::Error, 3).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let mut stepper = Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .into_stepper()?;
+    for _ in 0..3 {
+        stepper.step().await?;
+    }
+    match stepper.step().await {
+        Err(ModelError::Step(4, ActorError::Client(_, message))) => {
+            assert_eq!(message, "fault injected at step 3")
+        }
+        _ => panic!("expected a client failure"),
+    }
+    assert_eq!(logging.lock().await.len(), 3);
+    # Ok::<(), ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    Error,
+}
+
+/// Client wrapper injecting a [Fault] from the client update #`step`
+pub struct FaultInjector<C> {
+    client: C,
+    fault: Fault,
+    step: usize,
+    n_update: usize,
+}
+impl<C> FaultInjector<C> {
+    /// Creates a new fault injector for `client`
+    pub fn new(client: C, fault: Fault, step: usize) -> Self {
+        Self {
+            client,
+            fault,
+            step,
+            n_update: 0,
+        }
+    }
+    /// Returns the wrapped client
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+    // Checks if the fault has been triggered
+    fn faulty(&self, fault: Fault) -> bool {
+        self.fault == fault && self.n_update > self.step
+    }
+}
+impl<C: Update> Update for FaultInjector<C> {
+    fn update(&mut self) {
+        self.n_update += 1;
+        if self.n_update == self.step + 1 {
+            log::warn!("fault injected: {:?} at step {}", self.fault, self.step);
+        }
+        self.client.update();
+    }
+    fn tick(&mut self, clock: &SimClock) {
+        self.client.tick(clock);
+    }
+    fn seed(&mut self, seed: u64) {
+        self.client.seed(seed);
+    }
+    fn finished(&self) -> bool {
+        self.faulty(Fault::Stop) || self.client.finished()
+    }
+    fn failure(&self) -> Option<String> {
+        if self.faulty(Fault::Error) {
+            Some(format!("fault injected at step {}", self.step))
+        } else {
+            self.client.failure()
+        }
+    }
+}
+impl<C: Read<T, U>, T, U> Read<T, U> for FaultInjector<C> {
+    fn read(&mut self, data: Arc<Data<T, U>>) {
+        self.client.read(data);
+    }
+}
+impl<C: Write<T, U>, T, U> Write<T, U> for FaultInjector<C> {
+    fn write(&mut self) -> Option<Arc<Data<T, U>>> {
+        if self.faulty(Fault::Disconnect) {
+            None
+        } else {
+            self.client.write()
+        }
+    }
+}
@@ -84,7 +84,7 @@ Model::new(vec![Box::new(source),
        .wait()
        .await?;
 # Ok::<(), anyhow::Error>(())
-# });
+# }).unwrap();
 ```
 */
 
@@ -93,10 +93,232 @@ use crate::{
     Update,
 };
 use fem::{
-    dos::{DiscreteModalSolver, Get, Set, Solver},
+    dos::{DiscreteModalSolver, DiscreteStateSpace, Get, Set, Solver},
     fem_io,
 };
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, path::Path, sync::Arc};
+
+#[derive(Debug, thiserror::Error)]
+pub enum FemConfigError {
+    #[error("cannot open FEM inputs/outputs config file")]
+    Io(#[from] std::io::Error),
+    #[error("cannot parse FEM inputs/outputs config")]
+    Json(#[from] serde_json::Error),
+    #[error("unknown FEM input: {0}")]
+    UnknownInput(String),
+    #[error("unknown FEM output: {0}")]
+    UnknownOutput(String),
+}
+
+/**
+FEM inputs and outputs selection
+
+The inputs and outputs are given by the names of the [fem_io] types,
+they are added to the [DiscreteStateSpace] builder in the order they are listed.
+
+# Example
+The config file:
+```json
+{
+  "inputs": ["OSSElDriveTorque", "OSSAzDriveTorque", "OSSRotDriveTorque"],
+  "outputs": ["OSSAzEncoderAngle", "OSSElEncoderAngle", "OSSRotEncoderAngle"]
+}
+```
+is used to build the FEM state space model with:
+```no_run
+use dos_actors::clients::fem::FemIoConfig;
+use fem::{
+    dos::{DiscreteModalSolver, ExponentialMatrix},
+    FEM,
+};
+let config = FemIoConfig::from_json("fem_io.json")?;
+let fem = FEM::from_env()?.static_from_env()?;
+let n_io = (fem.n_inputs(), fem.n_outputs());
+let state_space = config
+    .select(
+        DiscreteModalSolver::<ExponentialMatrix>::from_fem(fem)
+            .sampling(1e3)
+            .proportional_damping(2. / 100.),
+    )?
+    .use_static_gain_compensation(n_io)
+    .build()?;
+# Ok::<(), anyhow::Error>(())
+```
+*/
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FemIoConfig {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+impl FemIoConfig {
+    /// Reads the inputs and outputs selection from a JSON file
+    pub fn from_json<P: AsRef<Path>>(path: P) -> Result<Self, FemConfigError> {
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+    /// Adds the inputs and outputs to the [DiscreteStateSpace] builder
+    pub fn select<'a, S>(
+        &self,
+        state_space: DiscreteStateSpace<'a, S>,
+    ) -> Result<DiscreteStateSpace<'a, S>, FemConfigError>
+    where
+        S: Solver + Default,
+    {
+        let state_space = self
+            .inputs
+            .iter()
+            .try_fold(state_space, |state_space, name| {
+                registry::ins(state_space, name)
+            })?;
+        self.outputs
+            .iter()
+            .try_fold(state_space, |state_space, name| {
+                registry::outs(state_space, name)
+            })
+    }
+}
+
+// Maps the names of the FEM inputs and outputs to the [fem_io] types
+mod registry {
+    use super::{fem_io::*, DiscreteStateSpace, FemConfigError, Solver};
+    macro_rules! registry {
+        ($fn:ident, $err:ident, $($name:ident),+) => {
+            pub fn $fn<'a, S>(
+                state_space: DiscreteStateSpace<'a, S>,
+                name: &str,
+            ) -> Result<DiscreteStateSpace<'a, S>, FemConfigError>
+            where
+                S: Solver + Default,
+            {
+                match name {
+                    $(stringify!($name) => Ok(state_space.$fn::<$name>()),)+
+                    _ => Err(FemConfigError::$err(name.to_string())),
+                }
+            }
+        };
+    }
+    registry!(
+        ins,
+        UnknownInput,
+        OSSElDriveTorque,
+        OSSAzDriveTorque,
+        OSSRotDriveTorque,
+        OSSHarpointDeltaF,
+        M1ActuatorsSegment1,
+        M1ActuatorsSegment2,
+        M1ActuatorsSegment3,
+        M1ActuatorsSegment4,
+        M1ActuatorsSegment5,
+        M1ActuatorsSegment6,
+        M1ActuatorsSegment7,
+        MCM2SmHexF,
+        MCM2PZTF,
+        MCM2LclForce6F,
+        OSSM1Lcl6F,
+        CFD2021106F
+    );
+    registry!(
+        outs,
+        UnknownOutput,
+        OSSAzEncoderAngle,
+        OSSElEncoderAngle,
+        OSSRotEncoderAngle,
+        OSSHardpointD,
+        M1Segment1AxialD,
+        M1Segment2AxialD,
+        M1Segment3AxialD,
+        M1Segment4AxialD,
+        M1Segment5AxialD,
+        M1Segment6AxialD,
+        M1Segment7AxialD,
+        MCM2SmHexD,
+        MCM2PZTD,
+        MCM2Lcl6D,
+        OSSM1Lcl,
+        OSSM1EdgeSensors,
+        OSSPayloads6D,
+        PMT3D
+    );
+}
+
+/**
+Velocity of the [OSSM1Lcl](fem_io::OSSM1Lcl) rigid body motions
+
+The modal velocities are private to the [Solver] of each mode of the [DiscreteModalSolver],
+so the [DiscreteModalSolver] cannot write the velocities derived from the modal states.
+Instead, the velocities are approximated from the displacements with a [Derivative](crate::clients::Derivative) client.
+The backward finite difference of the displacements is the velocity half a sample earlier.
+
+# Example
+The velocity of M1 segments moving at a constant speed along the 42 rigid body motions
+```
+# tokio_test::block_on(async {
+use dos_actors::{
+    clients::{fem::OSSM1LclVel, Derivative},
+    prelude::*,
+};
+use fem::fem_io::OSSM1Lcl;
+let sampling_frequency_hz = 1e3;
+let mut source: Initiator<_> = Signals::new(42, 100)
+    .signals(Signal::Ramp { a: 1e-3, b: 0. })
+    .into();
+let mut m1_rbm_velocity: Actor<_> =
+    Derivative::<OSSM1Lcl, OSSM1LclVel>::new(sampling_frequency_hz).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source
+    .add_output()
+    .build::<Vec<f64>, OSSM1Lcl>()
+    .into_input(&mut m1_rbm_velocity);
+m1_rbm_velocity
+    .add_output()
+    .build::<Vec<f64>, OSSM1LclVel>()
+    .into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(m1_rbm_velocity), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert!(logging
+    .lock()
+    .await
+    .chunks()
+    .skip(1)
+    .flatten()
+    .all(|v| (v - 1.).abs() < 1e-9));
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub enum OSSM1LclVel {}
+/// Velocity of the [MCM2Lcl6D](fem_io::MCM2Lcl6D) rigid body motions, see [OSSM1LclVel]
+pub enum MCM2Lcl6DVel {}
+
+/**
+M2 segments rigid body motions relative to the M1 segments
+
+The [M2RelativeM1] client writes the difference between the [MCM2Lcl6D](fem_io::MCM2Lcl6D)
+and the [OSSM1Lcl](fem_io::OSSM1Lcl) rigid body motions of each segment.
+
+# Example
+```no_run
+use dos_actors::{
+    clients::fem::{M2RelativeM1, M2RelativeM1Rbm},
+    prelude::*,
+};
+let mut relative: Actor<_> = M2RelativeM1::new().into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+relative
+    .add_output()
+    .build::<Vec<f64>, M2RelativeM1Rbm>()
+    .into_input(&mut sink);
+```
+*/
+pub enum M2RelativeM1Rbm {}
+/// [RelativeMotion](crate::clients::RelativeMotion) client of the M2 segments with respect to the M1 segments
+pub type M2RelativeM1 = crate::clients::RelativeMotion<fem_io::OSSM1Lcl, fem_io::MCM2Lcl6D>;
+crate::impl_relative_motion!(fem_io::OSSM1Lcl, fem_io::MCM2Lcl6D);
 
 impl<S> Update for DiscreteModalSolver<S>
 where
@@ -0,0 +1,227 @@
+/*!
+# Actor client for HDF5
+
+A simulation data logger that records the data in memory and
+automatically saves the data into a [HDF5] file (`data.h5`) at the end of a simulation.
+
+Each entry is saved as a 2D dataset named after the entry type, of shape `(number of samples, entry size)`,
+with the sampling frequency of the entry stored as the `sampling_frequency_hz` attribute of the dataset.
+
+The [Hdf5] client is enabled with the `hdf5` feature.
+
+[HDF5]: https://docs.rs/hdf5
+
+# Example
+
+A HDF5 logger with a 1kHz entry and a 200Hz entry of 2 elements each
+```
+# tokio_test::block_on(async {
+use dos_actors::clients::hdf5_client::Hdf5;
+use dos_actors::prelude::*;
+enum Fast {};
+enum Slow {};
+let n_step = 1000;
+let path = std::env::temp_dir().join("data.h5");
+let mut source: Initiator<_> = Signals::new(2, n_step).signals(Signal::Ramp { a: 1., b: 0. }).into();
+let mut sampler: Actor<_, 1, 5> = Sampler::<Vec<f64>, Slow>::default().into();
+let logging = Hdf5::builder(1e3)
+    .entry::<Fast>(2)
+    .entry_with_rate::<Slow>(2, 5)
+    .filename(path.to_str().unwrap())
+    .build()
+    .into_arcx();
+let mut fast_sink = Terminator::<_>::new(logging.clone());
+let mut slow_sink = Terminator::<_, 5>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Fast>().into_input(&mut fast_sink);
+source.add_output().build::<Vec<f64>, Slow>().into_input(&mut sampler);
+sampler.add_output().build::<Vec<f64>, Slow>().into_input(&mut slow_sink);
+Model::new(vec![
+    Box::new(source),
+    Box::new(sampler),
+    Box::new(fast_sink),
+    Box::new(slow_sink),
+])
+.check()?
+.run()
+.wait()
+.await?;
+drop(logging);
+let file = hdf5::File::open(&path)?;
+let slow = file.dataset("Slow")?;
+assert_eq!(slow.shape(), vec![200, 2]);
+assert_eq!(
+    slow.attr("sampling_frequency_hz")?.read_scalar::<f64>()?,
+    200.
+);
+# Ok::<(), anyhow::Error>(())
+# }).unwrap();
+```
+*/
+
+use crate::{
+    io::{Data, Read},
+    Update,
+};
+use std::{
+    any::{type_name, TypeId},
+    fmt::Display,
+    path::Path,
+    sync::Arc,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Hdf5Error {
+    #[error("cannot save data to HDF5")]
+    Hdf5(#[from] hdf5::Error),
+}
+
+type Result<T> = std::result::Result<T, Hdf5Error>;
+
+// A logger entry
+struct Entry {
+    id: TypeId,
+    name: String,
+    size: usize,
+    rate: usize,
+    data: Vec<f64>,
+}
+impl Entry {
+    // Number of samples
+    fn len(&self) -> usize {
+        self.data.len() / self.size
+    }
+}
+
+/// HDF5 format logger builder
+pub struct Hdf5Builder {
+    sampling_frequency_hz: f64,
+    entries: Vec<Entry>,
+    filename: Option<String>,
+}
+impl Hdf5Builder {
+    /// Creates a new HDF5 logger builder
+    pub fn new(sampling_frequency_hz: f64) -> Self {
+        Self {
+            sampling_frequency_hz,
+            entries: Vec::new(),
+            filename: None,
+        }
+    }
+    /// Adds an entry of `size` elements to the logger
+    pub fn entry<U: 'static>(self, size: usize) -> Self {
+        self.entry_with_rate::<U>(size, 1)
+    }
+    /// Adds an entry of `size` elements to the logger that is written every `rate` simulation steps
+    pub fn entry_with_rate<U: 'static>(self, size: usize, rate: usize) -> Self {
+        assert!(size > 0, "the entry size must be greater than 0");
+        assert!(rate > 0, "the entry rate must be greater than 0");
+        let mut entries = self.entries;
+        entries.push(Entry {
+            id: TypeId::of::<U>(),
+            name: type_name::<U>()
+                .split("::")
+                .last()
+                .unwrap_or("no name")
+                .to_string(),
+            size,
+            rate,
+            data: Vec::new(),
+        });
+        Self { entries, ..self }
+    }
+    /// Sets the name of the file to save the data to (default: "data.h5")
+    pub fn filename<S: Into<String>>(self, filename: S) -> Self {
+        Self {
+            filename: Some(filename.into()),
+            ..self
+        }
+    }
+    /// Builds the HDF5 logger
+    pub fn build(self) -> Hdf5 {
+        Hdf5 {
+            sampling_frequency_hz: self.sampling_frequency_hz,
+            entries: self.entries,
+            filename: self.filename.unwrap_or_else(|| "data.h5".to_string()),
+        }
+    }
+}
+
+/// HDF5 format logger
+pub struct Hdf5 {
+    sampling_frequency_hz: f64,
+    entries: Vec<Entry>,
+    filename: String,
+}
+impl Hdf5 {
+    /// Creates a new [HDF5](https://docs.rs/hdf5) data logger
+    ///
+    ///  - `sampling_frequency_hz`: the simulation sampling frequency
+    pub fn builder(sampling_frequency_hz: f64) -> Hdf5Builder {
+        Hdf5Builder::new(sampling_frequency_hz)
+    }
+    /// Saves the entries into a HDF5 file
+    pub fn to_hdf5<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let file = hdf5::File::create(path)?;
+        file.new_attr::<f64>()
+            .shape(())
+            .create("sampling_frequency_hz")?
+            .write_scalar(&self.sampling_frequency_hz)?;
+        for entry in &self.entries {
+            let dataset = file
+                .new_dataset::<f64>()
+                .shape((entry.len(), entry.size))
+                .create(entry.name.as_str())?;
+            dataset.write_raw(entry.data.as_slice())?;
+            dataset
+                .new_attr::<f64>()
+                .shape(())
+                .create("sampling_frequency_hz")?
+                .write_scalar(&(self.sampling_frequency_hz / entry.rate as f64))?;
+        }
+        Ok(())
+    }
+}
+impl Display for Hdf5 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "HDF5 logger:")?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "   - {:>8}: {:>4}x{:<6} @ {}Hz",
+                entry.name,
+                entry.len(),
+                entry.size,
+                self.sampling_frequency_hz / entry.rate as f64
+            )?;
+        }
+        Ok(())
+    }
+}
+impl Drop for Hdf5 {
+    fn drop(&mut self) {
+        log::info!("{self}");
+        if let Err(e) = self.to_hdf5(&self.filename) {
+            println!("{e}");
+        }
+    }
+}
+
+impl Update for Hdf5 {}
+impl<U: 'static> Read<Vec<f64>, U> for Hdf5 {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.id == TypeId::of::<U>())
+        {
+            Some(entry) if data.len() == entry.size => entry.data.extend_from_slice(&data),
+            Some(entry) => log::warn!(
+                "HDF5 entry {}: expected {} elements, received {}",
+                entry.name,
+                entry.size,
+                data.len()
+            ),
+            None => log::warn!("no HDF5 entry for {}", type_name::<U>()),
+        }
+    }
+}
@@ -23,6 +23,12 @@ use crate::{
 use lom::LOM;
 use std::{convert::AsMut, sync::Arc};
 
+#[doc(inline)]
+pub use super::wavefront::{
+    EncircledEnergy, SegmentPiston, SegmentTipTilt, Strehl, TipTilt, WavefrontError,
+    WavefrontMetrics,
+};
+
 impl Update for LOM {}
 
 #[cfg(feature = "fem")]
@@ -52,15 +58,11 @@ impl Read<Vec<f64>, fem::fem_io::MCM2Lcl6D> for LOM {
     }
 }
 
-/// Tip and tilt in the GMT focal plane
-pub enum TipTilt {}
 impl Write<Vec<f64>, TipTilt> for LOM {
     fn write(&mut self) -> Option<Arc<Data<Vec<f64>, TipTilt>>> {
         Some(Arc::new(Data::new((*self.tiptilt()).clone())))
     }
 }
-/// Segment tip and tilt in the GMT focal plane
-pub enum SegmentTipTilt {}
 impl Write<Vec<f64>, SegmentTipTilt> for LOM {
     fn write(&mut self) -> Option<Arc<Data<Vec<f64>, SegmentTipTilt>>> {
         Some(Arc::new(Data::new((*self.segment_tiptilt()).clone())))
@@ -72,10 +74,8 @@ impl Write<Vec<f64>, crate::clients::fsm::TTFB> for LOM {
         Some(Arc::new(Data::new((*self.segment_tiptilt()).clone())))
     }
 }
-/// Segment piston in the GMT exit pupil
-pub enum SegmentPiston {}
 impl Write<Vec<f64>, SegmentPiston> for LOM {
     fn write(&mut self) -> Option<Arc<Data<Vec<f64>, SegmentPiston>>> {
-        Some(Arc::new(Data::new((*self.segment_tiptilt()).clone())))
+        Some(Arc::new(Data::new((*self.segment_piston()).clone())))
     }
 }
@@ -146,6 +146,125 @@ macro_rules! impl_segments {
 }
 impl_segments! {1,2,3,4,5,6,7}
 
+/**
+M1 segments actuators force loop controllers
+
+[Segment](actuators::Segment)`<ID>` maps the segment number `ID` (1 to 7) to the segment
+actuators controller of the [m1-ctrl] crate and to the segment I/O types, such as the segments
+are created and connected without resorting to the `segment1..segment7` modules.
+
+# Example
+```
+use dos_actors::clients::m1::actuators::{Segment, SegmentActuators};
+use dos_actors::prelude::*;
+const M1_RATE: usize = 10;
+let mut m1_segment3: Actor<_, M1_RATE, 1> = Segment::<3>::new().into();
+assert_eq!(<Segment<3> as SegmentActuators>::ID, 3);
+```
+
+[m1-ctrl]: https://docs.rs/m1-ctrl/latest/m1_ctrl/
+*/
+pub mod actuators {
+    use super::*;
+    #[cfg(feature = "fem")]
+    use crate::{Actor, AddOuput, IntoInputs, Task};
+
+    /// M1 segment #`ID` actuators
+    pub struct Segment<const ID: usize>;
+    impl<const ID: usize> Segment<ID>
+    where
+        Self: SegmentActuators,
+    {
+        /// Creates the segment actuators controller
+        pub fn new() -> <Self as SegmentActuators>::Controller {
+            <Self as SegmentActuators>::controller()
+        }
+    }
+
+    /// Segment actuators controller and I/O types
+    pub trait SegmentActuators {
+        /// Segment number
+        const ID: usize;
+        /// [m1-ctrl](https://docs.rs/m1-ctrl/latest/m1_ctrl/) actuators controller
+        type Controller: 'static + Update + Send;
+        /// Hardpoints load cells input
+        type LoadCells: 'static + Send + Sync;
+        /// Actuators offset forces input
+        type OffsetForces: 'static + Send + Sync;
+        /// Actuators forces output to the FEM
+        #[cfg(feature = "fem")]
+        type Forces: 'static + Send + Sync;
+        /// Creates the segment actuators controller
+        fn controller() -> Self::Controller;
+        /**
+        Creates the segment actuators actor
+
+        The segment actuators forces are bootstrapped and sent to `fem`
+        and the hardpoints `load_cells` are sent to the segment.
+        */
+        #[cfg(feature = "fem")]
+        fn actor<C, const R: usize>(
+            load_cells: &mut Actor<hp_load_cells::Controller<'static>, 1, R>,
+            fem: &mut Actor<C>,
+        ) -> Actor<Self::Controller, R, 1>
+        where
+            Self::Controller: Read<Vec<f64>, Self::LoadCells> + Write<Vec<f64>, Self::Forces>,
+            hp_load_cells::Controller<'static>: Write<Vec<f64>, Self::LoadCells>,
+            C: 'static + Update + Send + Read<Vec<f64>, Self::Forces>,
+        {
+            let mut segment: Actor<Self::Controller, R, 1> = Self::controller().into();
+            load_cells
+                .add_output()
+                .build::<Vec<f64>, Self::LoadCells>()
+                .into_input(&mut segment);
+            segment
+                .add_output()
+                .bootstrap()
+                .build::<Vec<f64>, Self::Forces>()
+                .into_input(fem);
+            segment
+        }
+    }
+
+    macro_rules! impl_segment_actuators {
+        ($($sid:literal),+) => {
+            $(
+                paste! {
+                    impl SegmentActuators for Segment<$sid> {
+                        const ID: usize = $sid;
+                        type Controller = m1_ctrl::actuators::[<segment $sid>]::Controller<'static>;
+                        type LoadCells = [<S $sid HPLC>];
+                        type OffsetForces = [<S $sid SAoffsetFcmd>];
+                        #[cfg(feature = "fem")]
+                        type Forces = fem::fem_io::[<M1ActuatorsSegment $sid>];
+                        fn controller() -> Self::Controller {
+                            m1_ctrl::actuators::[<segment $sid>]::Controller::new()
+                        }
+                    }
+                }
+            )+
+
+            /**
+            Creates the 7 segments actuators actors
+
+            Each segment receives the hardpoints `load_cells` and sends its actuators forces to `fem`
+            (see [SegmentActuators::actor]).
+            */
+            #[cfg(feature = "fem")]
+            pub fn segments<C, const R: usize>(
+                load_cells: &mut Actor<hp_load_cells::Controller<'static>, 1, R>,
+                fem: &mut Actor<C>,
+            ) -> Vec<Box<dyn Task>>
+            where
+                C: 'static + Update + Send $(+ Read<Vec<f64>, <Segment<$sid> as SegmentActuators>::Forces>)+,
+            {
+                vec![$(Box::new(Segment::<$sid>::actor(load_cells, fem)) as Box<dyn Task>),+]
+            }
+        };
+    }
+    impl_segment_actuators! {1,2,3,4,5,6,7}
+}
+
 /*
 enum Segment<'a, const N: usize> {
     S1(Actor<actuators::segment1::Controller<'a>, N, 1>),
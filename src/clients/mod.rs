@@ -66,26 +66,36 @@ pub mod m1;
 #[cfg(feature = "apache-arrow")]
 pub mod arrow_client;
 
+#[cfg(feature = "hdf5")]
+pub mod hdf5_client;
+
 #[cfg(feature = "fsm")]
 pub mod fsm;
 
 #[cfg(feature = "crseo")]
 pub mod ceo;
 
+#[cfg(feature = "fault-injection")]
+pub mod fault;
 #[cfg(feature = "lom")]
 pub mod lom;
+pub mod wavefront;
 
 use crate::{
-    io::{Data, Read, Write},
+    io::{Data, Frame, Read, Write},
     Update,
 };
 use std::{
-    any::type_name,
+    any::{type_name, TypeId},
+    collections::HashMap,
     fmt::Display,
     marker::PhantomData,
     mem::take,
-    ops::{Add, Mul, Sub, SubAssign},
-    sync::Arc,
+    ops::{Add, Mul, Range, Sub, SubAssign},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 mod signals;
 #[doc(inline)]
@@ -99,6 +109,11 @@ pub struct Logging<T> {
     data: Vec<T>,
     n_sample: usize,
     n_entry: usize,
+    stamps: Vec<usize>,
+    warmup: usize,
+    n_skip: usize,
+    memory_budget: Option<usize>,
+    full: bool,
 }
 
 impl<T> std::ops::Deref for Logging<T> {
@@ -114,6 +129,11 @@ impl<T> Default for Logging<T> {
             n_entry: 1,
             data: Vec::new(),
             n_sample: 0,
+            stamps: Vec::new(),
+            warmup: 0,
+            n_skip: 0,
+            memory_budget: None,
+            full: false,
         }
     }
 }
@@ -122,6 +142,98 @@ impl<T> Logging<T> {
     pub fn n_entry(self, n_entry: usize) -> Self {
         Self { n_entry, ..self }
     }
+    /**
+    Discards the first `n` time samples
+
+    The warm-up samples are not counted in [Logging::len]
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 400).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let logging = Logging::<f64>::default().warmup(100).into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert_eq!(logging.lock().await.len(), 300);
+    assert_eq!(logging.lock().await[0], 100f64);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn warmup(self, n: usize) -> Self {
+        Self { warmup: n, ..self }
+    }
+    // Skips the warm-up samples of all the entries
+    fn warming_up(&mut self) -> bool {
+        if self.n_skip < self.warmup * self.n_entry {
+            self.n_skip += 1;
+            true
+        } else {
+            false
+        }
+    }
+    /**
+    Stops logging once the logged data would exceed `bytes`
+
+    The size of the data is the number of logged values times the size of `T`,
+    a time sample is either logged for all the entries or not at all.
+    A warning is issued when the budget is reached and the logging actor ends its loop,
+    stopping the actors upstream.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(2, 1000).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    // 10 f64 = 5 time samples of 2 values
+    let logging = Logging::<f64>::default()
+        .memory_budget(10 * std::mem::size_of::<f64>())
+        .into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let logging = logging.lock().await;
+    assert_eq!(logging.len(), 5);
+    assert_eq!(logging.chunks().last().unwrap(), &[4., 4.]);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn memory_budget(self, bytes: usize) -> Self {
+        Self {
+            memory_budget: Some(bytes),
+            ..self
+        }
+    }
+    // Checks at the start of each time sample that `n` more values of all the entries fit within the memory budget
+    fn over_budget(&mut self, n: usize) -> bool {
+        if self.full || !self.n_sample.is_multiple_of(self.n_entry) {
+            return self.full;
+        }
+        if let Some(budget) = self.memory_budget {
+            let size = (self.data.len() + n * self.n_entry) * std::mem::size_of::<T>();
+            if size > budget {
+                log::warn!(
+                    "Logging: memory budget of {budget} bytes reached after {} samples, logging stopped",
+                    self.len()
+                );
+                self.full = true;
+            }
+        }
+        self.full
+    }
     /// Pre-allocates the size of the vector holding the data
     pub fn capacity(self, capacity: usize) -> Self {
         Self {
@@ -132,13 +244,56 @@ impl<T> Logging<T> {
 }
 
 impl<T> Logging<T> {
-    /// Returns the # of time samples
+    /**
+    Returns the # of time samples
+
+    Returns 0 if nothing has been logged yet
+
+    # Example
+    ```
+    use dos_actors::prelude::*;
+    let logging = Logging::<f64>::default().n_entry(2);
+    assert_eq!(logging.len(), 0);
+    assert_eq!(logging.n_data(), 0);
+    assert_eq!(logging.chunks().count(), 0);
+    ```
+
+    # Panics
+    In debug builds, if the # of logged samples is not a multiple of the # of entries
+    ```should_panic
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 3).into();
+    let logging = Logging::<f64>::default().n_entry(2).into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    logging.lock().await.len();
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
     pub fn len(&self) -> usize {
+        debug_assert!(
+            self.n_sample.is_multiple_of(self.n_entry),
+            "Logging: {} samples cannot be evenly split into {} entries",
+            self.n_sample,
+            self.n_entry
+        );
         self.n_sample / self.n_entry
     }
-    /// Returns the sum of the entry sizes
+    /// Returns the sum of the entry sizes, 0 if nothing has been logged yet
     pub fn n_data(&self) -> usize {
-        self.data.len() / self.len()
+        self.data.len().checked_div(self.len()).unwrap_or_default()
+    }
+    /// Returns the steps of the [stamped](crate::io::Data::stamped) data in the order they were received
+    pub fn stamps(&self) -> &[usize] {
+        &self.stamps
     }
     /// Checks if the logger is empty
     pub fn is_empty(&self) -> bool {
@@ -146,7 +301,131 @@ impl<T> Logging<T> {
     }
     /// Returns data chunks the size of the entries
     pub fn chunks(&self) -> impl Iterator<Item = &[T]> {
-        self.data.chunks(self.n_data())
+        self.data.chunks(self.n_data().max(1))
+    }
+}
+
+/// [Logging] NumPy export error
+#[cfg(feature = "npy")]
+#[derive(Debug, thiserror::Error)]
+pub enum NpyError {
+    #[error("logging data shape mismatch")]
+    Shape(#[from] ndarray::ShapeError),
+    #[error("cannot write logging data to npy file")]
+    Write(#[from] ndarray_npy::WriteNpyError),
+}
+#[cfg(feature = "npy")]
+impl Logging<f64> {
+    /**
+    Writes the data into a NumPy `.npy` file
+
+    The data is saved as a 2D array of shape `(len(), n_data())`
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(2, 10).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let path = std::env::temp_dir().join("logging.npy");
+    logging.lock().await.to_npy(&path)?;
+    let header = std::fs::read(&path)?;
+    assert!(String::from_utf8_lossy(&header).contains("'shape': (10, 2)"));
+    # Ok::<(), anyhow::Error>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn to_npy<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), NpyError> {
+        let data = ndarray::ArrayView2::from_shape((self.len(), self.n_data()), &self.data)?;
+        ndarray_npy::write_npy(path, &data)?;
+        Ok(())
+    }
+}
+
+/// Step response metrics of a [Logging] channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelMetrics {
+    /// Mean of the samples in the final window
+    pub final_value: f64,
+    /// Index of the sample from which the channel stays within the tolerance band around the final value
+    pub settling_step: usize,
+    /// Largest excursion beyond the final value relative to the step amplitude (final minus initial value)
+    pub overshoot: f64,
+    /// Root mean square of the samples in the final window
+    pub rms: f64,
+}
+impl Logging<f64> {
+    /**
+    Computes the step response metrics of each channel
+
+    The final value of a channel is the mean of its last `final_window` samples and
+    the channel has settled once it stays within `tolerance` times the step amplitude of the final value.
+
+    # Example
+    The step response of a damped harmonic oscillator
+    ```
+    use dos_actors::{clients::Logging, io::{Data, Read}};
+    use std::{f64::consts::PI, sync::Arc};
+    enum Sig {};
+    let (zeta, omega, fs) = (0.5f64, 2. * PI, 1e3);
+    let omega_d = omega * (1. - zeta * zeta).sqrt();
+    let mut logging = Logging::<f64>::default();
+    for k in 0..10_000 {
+        let t = k as f64 / fs;
+        let x = 1.
+            - (-zeta * omega * t).exp()
+                * ((omega_d * t).cos() + zeta / (1. - zeta * zeta).sqrt() * (omega_d * t).sin());
+        <Logging<f64> as Read<Vec<f64>, Sig>>::read(&mut logging, Arc::new(Data::new(vec![x, 2.])));
+    }
+    let metrics = logging.metrics(0.02, 1000);
+    assert_eq!(metrics.len(), 2);
+    let overshoot = (-PI * zeta / (1. - zeta * zeta).sqrt()).exp();
+    assert!((metrics[0].overshoot - overshoot).abs() < 1e-4);
+    let settling_time = metrics[0].settling_step as f64 / fs;
+    assert!((settling_time - 4. / (zeta * omega)).abs() < 5e-2);
+    assert!((metrics[0].final_value - 1.).abs() < 1e-9);
+    assert_eq!(metrics[1].settling_step, 0);
+    assert_eq!(metrics[1].rms, 2.);
+    ```
+    */
+    pub fn metrics(&self, tolerance: f64, final_window: usize) -> Vec<ChannelMetrics> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let window = final_window.clamp(1, self.len());
+        (0..self.n_data())
+            .map(|i| {
+                let values: Vec<f64> = self.chunks().map(|data| data[i]).collect();
+                let last = &values[values.len() - window..];
+                let final_value = last.iter().sum::<f64>() / window as f64;
+                let amplitude = final_value - values[0];
+                let band = tolerance * amplitude.abs();
+                ChannelMetrics {
+                    final_value,
+                    settling_step: values
+                        .iter()
+                        .rposition(|x| (x - final_value).abs() > band)
+                        .map_or(0, |k| k + 1),
+                    overshoot: if amplitude == 0f64 {
+                        0f64
+                    } else {
+                        values
+                            .iter()
+                            .map(|x| (x - final_value) / amplitude)
+                            .fold(0f64, f64::max)
+                    },
+                    rms: (last.iter().map(|x| x * x).sum::<f64>() / window as f64).sqrt(),
+                }
+            })
+            .collect()
     }
 }
 
@@ -162,12 +441,388 @@ impl<T> Display for Logging<T> {
     }
 }
 
-impl<T> Update for Logging<T> {}
+/// [Logging] of [Frame]s, the frames are logged with their shape
+impl<T: Clone, U> Read<Frame<T>, U> for Logging<Frame<T>> {
+    fn read(&mut self, data: Arc<Data<Frame<T>, U>>) {
+        log::debug!("receive {} input: {:?}", type_name::<U>(), data.shape());
+        if self.warming_up() || self.over_budget(1) {
+            return;
+        }
+        self.data.push((**data).clone());
+        self.n_sample += 1;
+        self.stamps.extend(data.step());
+    }
+}
+/// [Logging] of boolean flags
+impl<U> Read<bool, U> for Logging<bool> {
+    fn read(&mut self, data: Arc<Data<bool, U>>) {
+        log::debug!("receive {} input: {:}", type_name::<U>(), **data);
+        if self.warming_up() || self.over_budget(1) {
+            return;
+        }
+        self.data.push(**data);
+        self.n_sample += 1;
+        self.stamps.extend(data.step());
+    }
+}
+impl<T> Update for Logging<T> {
+    fn finished(&self) -> bool {
+        self.full
+    }
+}
 impl<T: Clone, U> Read<Vec<T>, U> for Logging<T> {
     fn read(&mut self, data: Arc<Data<Vec<T>, U>>) {
         log::debug!("receive {} input: {:}", type_name::<U>(), data.len(),);
+        if self.warming_up() || self.over_budget(data.len()) {
+            return;
+        }
         self.data.extend((**data).clone());
         self.n_sample += 1;
+        self.stamps.extend(data.step());
+    }
+}
+
+/**
+Data logging by input type
+
+Each input type `U` is logged into a separate [Logging]
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::MultiLogging, prelude::*};
+enum Scalar {};
+enum Vector {};
+let mut scalar: Initiator<_> = Signals::new(1, 10).signals(Signal::Constant(1.)).into();
+let mut vector: Initiator<_> = Signals::new(3, 10).signals(Signal::Constant(2.)).into();
+let logging = MultiLogging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+scalar.add_output().build::<Vec<f64>, Scalar>().into_input(&mut sink);
+vector.add_output().build::<Vec<f64>, Vector>().into_input(&mut sink);
+Model::new(vec![Box::new(scalar), Box::new(vector), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let logs = logging.lock().await;
+assert_eq!(**logs.get::<Scalar>().unwrap(), vec![1f64; 10]);
+assert_eq!(**logs.get::<Vector>().unwrap(), vec![2f64; 30]);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct MultiLogging<T> {
+    loggings: HashMap<TypeId, Logging<T>>,
+}
+impl<T> Default for MultiLogging<T> {
+    fn default() -> Self {
+        Self {
+            loggings: HashMap::new(),
+        }
+    }
+}
+impl<T> MultiLogging<T> {
+    /// Returns the [Logging] of the input type `U`
+    pub fn get<U: 'static>(&self) -> Option<&Logging<T>> {
+        self.loggings.get(&TypeId::of::<U>())
+    }
+}
+impl<T> Update for MultiLogging<T> {}
+impl<T: Clone, U: 'static> Read<Vec<T>, U> for MultiLogging<T> {
+    fn read(&mut self, data: Arc<Data<Vec<T>, U>>) {
+        <Logging<T> as Read<Vec<T>, U>>::read(
+            self.loggings.entry(TypeId::of::<U>()).or_default(),
+            data,
+        );
+    }
+}
+
+/**
+Assembles several inputs into a single vector
+
+Each input type `U` is copied into its own slice of the assembled vector,
+the slice is registered with [Assembler::entry].
+An input without entry or which size does not match its entry is logged
+and the assembler stops writing.
+
+# Example
+Two 42 elements vectors assembled into a 84 elements vector
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Assembler, prelude::*};
+enum M1 {};
+enum M2 {};
+enum M12 {};
+let mut m1: Initiator<_> = Signals::new(42, 10).signals(Signal::Constant(1.)).into();
+let mut m2: Initiator<_> = Signals::new(42, 10).signals(Signal::Constant(2.)).into();
+let mut assembler: Actor<_> = Assembler::new(84).entry::<M1>(0..42).entry::<M2>(42..84).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+m1.add_output().build::<Vec<f64>, M1>().into_input(&mut assembler);
+m2.add_output().build::<Vec<f64>, M2>().into_input(&mut assembler);
+assembler.add_output().build::<Vec<f64>, M12>().into_input(&mut sink);
+Model::new(vec![Box::new(m1), Box::new(m2), Box::new(assembler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let logs = logging.lock().await;
+assert_eq!(logs.len(), 10);
+logs.chunks().for_each(|x| {
+    assert_eq!(x[..42], [1f64; 42]);
+    assert_eq!(x[42..], [2f64; 42]);
+});
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+
+An input of the wrong size stops the assembler
+```
+use dos_actors::{clients::Assembler, io::{Data, Read, Write}};
+use std::sync::Arc;
+enum M1 {};
+enum M12 {};
+let mut assembler = Assembler::new(4).entry::<M1>(0..2);
+assembler.read(Arc::new(Data::<Vec<f64>, M1>::new(vec![1.; 3])));
+assert!(<Assembler as Write<Vec<f64>, M12>>::write(&mut assembler).is_none());
+```
+*/
+#[derive(Debug)]
+pub struct Assembler {
+    data: Option<Vec<f64>>,
+    ranges: HashMap<TypeId, Range<usize>>,
+}
+impl Default for Assembler {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+impl Assembler {
+    /// Creates a new assembler for a vector of size `n`
+    pub fn new(n: usize) -> Self {
+        Self {
+            data: Some(vec![0f64; n]),
+            ranges: HashMap::new(),
+        }
+    }
+    /// Copies the input of type `U` into the `range` of the assembled vector
+    pub fn entry<U: 'static>(self, range: Range<usize>) -> Self {
+        let n = self.data.as_ref().map_or(0, Vec::len);
+        assert!(
+            range.end <= n,
+            "{} range {:?} is out of the assembled vector bounds (0..{})",
+            type_name::<U>(),
+            range,
+            n
+        );
+        let mut ranges = self.ranges;
+        ranges.insert(TypeId::of::<U>(), range);
+        Self { ranges, ..self }
+    }
+}
+impl Update for Assembler {}
+impl<U: 'static> Read<Vec<f64>, U> for Assembler {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        match self.ranges.get(&TypeId::of::<U>()) {
+            Some(range) if data.len() == range.len() => {
+                if let Some(assembled) = self.data.as_mut() {
+                    assembled[range.clone()].copy_from_slice(&data);
+                }
+            }
+            Some(range) => {
+                log::warn!(
+                    "{} size ({}) do not match the assembler entry size ({})",
+                    type_name::<U>(),
+                    data.len(),
+                    range.len()
+                );
+                self.data = None;
+            }
+            None => {
+                log::warn!("{} has no assembler entry", type_name::<U>());
+                self.data = None;
+            }
+        }
+    }
+}
+impl<U> Write<Vec<f64>, U> for Assembler {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, U>>> {
+        self.data.clone().map(|data| Arc::new(Data::new(data)))
+    }
+}
+
+/**
+Relative motion between two bodies
+
+Reads the motion of a reference body of input type `U` and of a moving body of input type `V`,
+and writes the element-wise difference of the moving body motion minus the reference body motion.
+Nothing is written until both motions have been read.
+
+The inputs are implemented for a given pair of `U` and `V` with the [impl_relative_motion](crate::impl_relative_motion) macro.
+
+# Example
+Relative rigid body motions of 7 segments between two mirrors
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::RelativeMotion, impl_relative_motion, prelude::*};
+enum M1Rbm {};
+enum M2Rbm {};
+enum M2M1Rbm {};
+impl_relative_motion!(M1Rbm, M2Rbm);
+// constant rigid body motions
+let rbm = |f: fn(usize) -> f64| {
+    (0..42).fold(Signals::new(42, 10), |signals, k| {
+        signals.output_signal(k, Signal::Constant(f(k)))
+    })
+};
+let mut m1: Initiator<_> = rbm(|k| k as f64).into();
+let mut m2: Initiator<_> = rbm(|k| (k % 6) as f64 * 10.).into();
+let mut relative: Actor<_> = RelativeMotion::<M1Rbm, M2Rbm>::new().into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+m1.add_output().build::<Vec<f64>, M1Rbm>().into_input(&mut relative);
+m2.add_output().build::<Vec<f64>, M2Rbm>().into_input(&mut relative);
+relative.add_output().build::<Vec<f64>, M2M1Rbm>().into_input(&mut sink);
+Model::new(vec![Box::new(m1), Box::new(m2), Box::new(relative), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let logs = logging.lock().await;
+assert_eq!(logs.len(), 10);
+for (segment, rbm) in logs.chunks().last().unwrap().chunks(6).enumerate() {
+    let expected: Vec<f64> = (0..6).map(|i| (i * 9) as f64 - (segment * 6) as f64).collect();
+    assert_eq!(rbm, expected.as_slice(), "segment #{}", segment + 1);
+}
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct RelativeMotion<U, V> {
+    reference: Option<Vec<f64>>,
+    body: Option<Vec<f64>>,
+    uid: PhantomData<(U, V)>,
+}
+impl<U, V> Default for RelativeMotion<U, V> {
+    fn default() -> Self {
+        Self {
+            reference: None,
+            body: None,
+            uid: PhantomData,
+        }
+    }
+}
+impl<U, V> RelativeMotion<U, V> {
+    /// Creates a new relative motion client of the body `V` with respect to the body `U`
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Sets the motion of the reference body `U`
+    pub fn set_reference(&mut self, data: &[f64]) {
+        self.reference = Some(data.to_vec());
+    }
+    /// Sets the motion of the moving body `V`
+    pub fn set_body(&mut self, data: &[f64]) {
+        self.body = Some(data.to_vec());
+    }
+}
+impl<U, V> Update for RelativeMotion<U, V> {}
+impl<U, V, W> Write<Vec<f64>, W> for RelativeMotion<U, V> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, W>>> {
+        let (Some(body), Some(reference)) = (&self.body, &self.reference) else {
+            return None;
+        };
+        if body.len() != reference.len() {
+            log::warn!(
+                "the motions of {} ({}) and {} ({}) do not have the same size",
+                type_name::<V>(),
+                body.len(),
+                type_name::<U>(),
+                reference.len()
+            );
+            return None;
+        }
+        Some(Arc::new(Data::new(
+            body.iter()
+                .zip(reference)
+                .map(|(body, reference)| body - reference)
+                .collect(),
+        )))
+    }
+}
+
+/**
+[Logging] of [nalgebra] vectors
+
+# Example
+A [DVector](nalgebra::DVector) round-trip through a [Sampler]
+```
+# tokio_test::block_on(async {
+use dos_actors::{io, prelude::*, Update};
+use nalgebra::DVector;
+use std::sync::Arc;
+struct Ramp(usize);
+impl Update for Ramp {}
+impl<U> io::Write<DVector<f64>, U> for Ramp {
+    fn write(&mut self) -> Option<Arc<io::Data<DVector<f64>, U>>> {
+        if self.0 == 10 {
+            return None;
+        }
+        self.0 += 1;
+        Some(Arc::new(io::Data::new(DVector::from_element(3, self.0 as f64))))
+    }
+}
+enum V {};
+let mut source: Initiator<_> = Ramp(0).into();
+let mut sampler: Actor<_> = Sampler::<DVector<f64>, V>::new(3).into();
+let logging = Logging::<f64>::default().n_entry(1).into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source
+    .add_output()
+    .build::<DVector<f64>, V>()
+    .into_input(&mut sampler);
+sampler
+    .add_output()
+    .build::<DVector<f64>, V>()
+    .into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let logs = logging.lock().await;
+assert_eq!(logs.len(), 10);
+assert_eq!(logs.n_data(), 3);
+assert_eq!(logs.chunks().last().unwrap(), &[10f64; 3]);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar, U> Read<nalgebra::DVector<T>, U> for Logging<T> {
+    fn read(&mut self, data: Arc<Data<nalgebra::DVector<T>, U>>) {
+        log::debug!("receive {} input: {:}", type_name::<U>(), data.len(),);
+        if self.warming_up() || self.over_budget(data.len()) {
+            return;
+        }
+        self.data.extend_from_slice(data.as_slice());
+        self.n_sample += 1;
+        self.stamps.extend(data.step());
+    }
+}
+/// [Logging] of [nalgebra] matrices, the matrices are logged in column-major order
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar, U> Read<nalgebra::DMatrix<T>, U> for Logging<T> {
+    fn read(&mut self, data: Arc<Data<nalgebra::DMatrix<T>, U>>) {
+        log::debug!("receive {} input: {:}", type_name::<U>(), data.len(),);
+        if self.warming_up() || self.over_budget(data.len()) {
+            return;
+        }
+        self.data.extend_from_slice(data.as_slice());
+        self.n_sample += 1;
+        self.stamps.extend(data.step());
     }
 }
 
@@ -176,12 +831,69 @@ impl<T: Clone, U> Read<Vec<T>, U> for Logging<T> {
 pub struct Sampler<T, U, V = U> {
     input: Arc<Data<T, U>>,
     output: PhantomData<V>,
+    fresh: bool,
+    n_hold: usize,
 }
 impl<T: Default, U, V> Default for Sampler<T, U, V> {
     fn default() -> Self {
+        Self::with_initial(T::default())
+    }
+}
+impl<T, U, V> Sampler<T, U, V> {
+    /**
+    Creates a new sampler initialized with `value`
+
+    `value` is the first sample written by the sampler, if the sampler output is bootstrapped.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(2, 10).into();
+    let mut sampler: Actor<_> = Sampler::<Vec<f64>, Sig>::with_initial(vec![1f64, 2f64]).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+    sampler
+        .add_output()
+        .bootstrap()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert_eq!(logging.lock().await.chunks().next().unwrap(), &[1f64, 2f64]);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn with_initial(value: T) -> Self {
+        Self {
+            input: Arc::new(Data::new(value)),
+            output: PhantomData,
+            fresh: true,
+            n_hold: 0,
+        }
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T, U, V> Sampler<nalgebra::DVector<T>, U, V>
+where
+    T: nalgebra::Scalar + Default,
+{
+    /// Creates a new [nalgebra::DVector] sampler of size `n_data` initialized with `T::default()`
+    pub fn new(n_data: usize) -> Self {
         Self {
-            input: Arc::new(Data::new(T::default())),
+            input: Arc::new(Data::new(nalgebra::DVector::from_element(
+                n_data,
+                T::default(),
+            ))),
             output: PhantomData,
+            fresh: true,
+            n_hold: 0,
         }
     }
 }
@@ -189,6 +901,7 @@ impl<T, U, V> Update for Sampler<T, U, V> {}
 impl<T, U, V> Read<T, U> for Sampler<T, U, V> {
     fn read(&mut self, data: Arc<Data<T, U>>) {
         self.input = data;
+        self.fresh = true;
     }
 }
 impl<T: Clone, U, V> Write<T, V> for Sampler<T, U, V> {
@@ -196,76 +909,619 @@ impl<T: Clone, U, V> Write<T, V> for Sampler<T, U, V> {
         Some(Arc::new(Data::new((**self.input).clone())))
     }
 }
+impl<T, U, V> Sampler<T, U, V> {
+    // Counts the consecutive writes of the same input
+    fn hold_count(&mut self) -> usize {
+        if self.fresh {
+            self.fresh = false;
+            self.n_hold = 0;
+        } else {
+            self.n_hold += 1;
+        }
+        self.n_hold
+    }
+}
+/**
+[Sampler] hold count
 
-/// Concatenates data into a [Vec]
-pub struct Concat<T>(Vec<T>);
-impl<T: Default> Default for Concat<T> {
+The # of times the [Sampler] has written the same input since the last fresh input
+
+# Example
+An input updated every 3 steps
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::HoldCount, io, prelude::*, Update};
+use std::sync::Arc;
+#[derive(Default)]
+struct Counts(Vec<usize>);
+impl Update for Counts {}
+impl io::Read<usize, HoldCount> for Counts {
+    fn read(&mut self, data: Arc<io::Data<usize, HoldCount>>) {
+        self.0.push(**data);
+    }
+}
+enum Sig {};
+let mut source: Initiator<_, 3> = Signals::new(1, 4).into();
+let mut sampler: Actor<_, 3, 1> = Sampler::<Vec<f64>, Sig>::default().into();
+let counts = Counts::default().into_arcx();
+let mut sink = Terminator::<_>::new(counts.clone());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+sampler.add_output().build::<usize, HoldCount>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(counts.lock().await.0, vec![0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2]);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub enum HoldCount {}
+impl<T, U, V> Write<usize, HoldCount> for Sampler<Vec<T>, U, V> {
+    fn write(&mut self) -> Option<Arc<Data<usize, HoldCount>>> {
+        Some(Arc::new(Data::new(self.hold_count())))
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T, U, V> Write<usize, HoldCount> for Sampler<nalgebra::DVector<T>, U, V> {
+    fn write(&mut self) -> Option<Arc<Data<usize, HoldCount>>> {
+        Some(Arc::new(Data::new(self.hold_count())))
+    }
+}
+
+/**
+Sample-and-hold rate transitionner for rates that are not integer multiples of each other
+
+Used as the client of a [fractional](crate::Actor::fractional) actor `Actor<_, NI, NO>` which inputs rate `NI` and outputs rate `NO` are not integer multiples of each other,
+e.g. `Actor<_, 3, 5>` converts a 500Hz input into a 300Hz output within a 1.5kHz simulation.
+The actor keeps track of the offset between the input and output sampling times, like the error term of Bresenham's line algorithm,
+and after each input it writes as many outputs as there are output sampling times within the input period,
+so each output is the most recent input at the output sampling time.
+Over `lcm(NI,NO)` simulation steps, the actor reads `lcm(NI,NO)/NI` inputs and writes `lcm(NI,NO)/NO` outputs.
+
+# Example
+A 3:2 downsampling and a 2:3 upsampling
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::FractionalSampler, prelude::*};
+enum Sig {};
+// 3:2
+let mut source: Initiator<_, 2> = Signals::new(1, 300).signals(Signal::Ramp { a: 1., b: 0. }).into();
+let mut sampler: Actor<_, 2, 3> = Actor::from(FractionalSampler::<Vec<f64>, Sig>::default()).fractional();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_, 3>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+sampler.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(logging.lock().await.len(), 200);
+assert_eq!(logging.lock().await[..4], [0., 1., 3., 4.]);
+// 2:3
+let mut source: Initiator<_, 3> = Signals::new(1, 200).signals(Signal::Ramp { a: 1., b: 0. }).into();
+let mut sampler: Actor<_, 3, 2> = Actor::from(FractionalSampler::<Vec<f64>, Sig>::default()).fractional();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_, 2>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+sampler.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(logging.lock().await.len(), 300);
+assert_eq!(logging.lock().await[..6], [0., 0., 1., 2., 2., 3.]);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct FractionalSampler<T, U, V = U>(Sampler<T, U, V>);
+impl<T: Default, U, V> Default for FractionalSampler<T, U, V> {
     fn default() -> Self {
-        Self(Vec::new())
+        Self(Sampler::default())
     }
 }
-impl<T> Update for Concat<T> {}
-impl<T: Clone + Default, U> Read<T, U> for Concat<T> {
+impl<T, U, V> FractionalSampler<T, U, V> {
+    /// Creates a new fractional sampler initialized with `value`
+    pub fn with_initial(value: T) -> Self {
+        Self(Sampler::with_initial(value))
+    }
+}
+impl<T, U, V> Update for FractionalSampler<T, U, V> {}
+impl<T, U, V> Read<T, U> for FractionalSampler<T, U, V> {
     fn read(&mut self, data: Arc<Data<T, U>>) {
-        self.0.push((*data).clone());
+        self.0.read(data);
     }
 }
-impl<T: Clone, U> Write<Vec<T>, U> for Concat<T> {
-    fn write(&mut self) -> Option<Arc<Data<Vec<T>, U>>> {
-        Some(Arc::new(Data::new(take(&mut self.0))))
+impl<T: Clone, U, V> Write<T, V> for FractionalSampler<T, U, V> {
+    fn write(&mut self) -> Option<Arc<Data<T, V>>> {
+        self.0.write()
     }
 }
 
-/// Integral controller
-#[derive(Default)]
-pub struct Integrator<T, U> {
-    gain: Vec<T>,
-    mem: Vec<T>,
-    zero: Vec<T>,
+/// [Decimator] sample selection over the decimation window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decimation {
+    /// First sample of the window
+    First,
+    /// Last sample of the window
+    Last,
+    /// Average of the samples of the window
+    Mean,
+}
+/**
+Decimation with a choice of the sample written from the decimation window
+
+Used as the client of a decimating actor `Actor<_, NI, NO>` with `NO>NI`,
+the decimation window is made of the `NO/NI` samples read in between 2 writes.
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{
+    clients::{Decimation, Decimator},
+    prelude::*,
+};
+enum Sig {};
+let mut source: Initiator<_> = Signals::new(1, 50).signals(Signal::Ramp { a: 1., b: 0. }).into();
+let mut last: Actor<_, 1, 5> = Decimator::<Sig>::new(Decimation::Last).into();
+let mut mean: Actor<_, 1, 5> = Decimator::<Sig>::new(Decimation::Mean).into();
+let last_logging = Logging::<f64>::default().into_arcx();
+let mut last_sink = Terminator::<_, 5>::new(last_logging.clone());
+let mean_logging = Logging::<f64>::default().into_arcx();
+let mut mean_sink = Terminator::<_, 5>::new(mean_logging.clone());
+source
+    .add_output()
+    .multiplex(2)
+    .build::<Vec<f64>, Sig>()
+    .into_input(&mut last)
+    .into_input(&mut mean);
+last.add_output().build::<Vec<f64>, Sig>().into_input(&mut last_sink);
+mean.add_output().build::<Vec<f64>, Sig>().into_input(&mut mean_sink);
+Model::new(vec![
+    Box::new(source),
+    Box::new(last),
+    Box::new(mean),
+    Box::new(last_sink),
+    Box::new(mean_sink),
+])
+.check()?
+.run()
+.wait()
+.await?;
+let windows: Vec<_> = (0..10).map(|i| 5. * i as f64).collect();
+assert_eq!(
+    **last_logging.lock().await,
+    windows.iter().map(|x| x + 4.).collect::<Vec<_>>()
+);
+assert_eq!(
+    **mean_logging.lock().await,
+    windows.iter().map(|x| x + 2.).collect::<Vec<_>>()
+);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct Decimator<U, V = U> {
+    mode: Decimation,
+    window: Vec<f64>,
+    n_sample: usize,
     uid: PhantomData<U>,
+    output: PhantomData<V>,
 }
-impl<T, U> Integrator<T, U>
-where
-    T: Default + Clone,
-{
-    /// Creates a new integral controller
-    pub fn new(n_data: usize) -> Self {
+impl<U, V> Decimator<U, V> {
+    /// Creates a new decimator
+    pub fn new(mode: Decimation) -> Self {
         Self {
-            gain: vec![Default::default(); n_data],
-            mem: vec![Default::default(); n_data],
-            zero: vec![Default::default(); n_data],
+            mode,
+            window: Vec::new(),
+            n_sample: 0,
             uid: PhantomData,
+            output: PhantomData,
         }
     }
-    /// Sets a unique gain
-    pub fn gain(self, gain: T) -> Self {
+}
+impl<U, V> Update for Decimator<U, V> {}
+impl<U, V> Read<Vec<f64>, U> for Decimator<U, V> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        match self.mode {
+            _ if self.n_sample == 0 => self.window = (**data).clone(),
+            Decimation::First => (),
+            Decimation::Last => self.window = (**data).clone(),
+            Decimation::Mean => self
+                .window
+                .iter_mut()
+                .zip(data.iter())
+                .for_each(|(w, x)| *w += x),
+        }
+        self.n_sample += 1;
+    }
+}
+impl<U, V> Write<Vec<f64>, V> for Decimator<U, V> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, V>>> {
+        if self.mode == Decimation::Mean && self.n_sample > 1 {
+            let n = self.n_sample as f64;
+            self.window.iter_mut().for_each(|w| *w /= n);
+        }
+        self.n_sample = 0;
+        Some(Arc::new(Data::new(self.window.clone())))
+    }
+}
+
+/**
+Discrete time derivative
+
+The derivative is the backward finite difference of consecutive inputs
+multiplied by the sampling frequency, the derivative of the first input is zero.
+
+# Example
+The velocity of a sinusoidal displacement
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Derivative, prelude::*};
+use std::f64::consts::PI;
+enum Displacement {};
+enum Velocity {};
+let sampling_frequency_hz = 1000f64;
+let frequency_hz = 5f64;
+let mut source: Initiator<_> = Signals::new(1, 1000)
+    .signals(Signal::Sinusoid {
+        amplitude: 1.,
+        sampling_frequency_hz,
+        frequency_hz,
+        phase_s: 0.,
+    })
+    .into();
+let mut derivative: Actor<_> =
+    Derivative::<Displacement, Velocity>::new(sampling_frequency_hz).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source
+    .add_output()
+    .build::<Vec<f64>, Displacement>()
+    .into_input(&mut derivative);
+derivative
+    .add_output()
+    .build::<Vec<f64>, Velocity>()
+    .into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(derivative), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let omega = 2. * PI * frequency_hz;
+let dt = sampling_frequency_hz.recip();
+for (k, v) in logging.lock().await.iter().enumerate().skip(1) {
+    // the backward difference is the velocity half a sample earlier
+    let t = (k as f64 - 0.5) * dt;
+    assert!((v - omega * (omega * t).cos()).abs() < 1e-2, "{k}: {v}");
+}
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct Derivative<U, V = U> {
+    sampling_frequency_hz: f64,
+    previous: Option<Vec<f64>>,
+    rate: Vec<f64>,
+    uid: PhantomData<U>,
+    output: PhantomData<V>,
+}
+impl<U, V> Derivative<U, V> {
+    /// Creates a new derivative client for inputs sampled at `sampling_frequency_hz`
+    pub fn new(sampling_frequency_hz: f64) -> Self {
         Self {
-            gain: vec![gain; self.mem.len()],
-            ..self
+            sampling_frequency_hz,
+            previous: None,
+            rate: Vec::new(),
+            uid: PhantomData,
+            output: PhantomData,
         }
     }
-    /// Sets the gain vector
-    pub fn gain_vector(self, gain: Vec<T>) -> Self {
-        assert_eq!(
-            gain.len(),
-            self.mem.len(),
-            "gain vector length error: expected {} found {}",
-            gain.len(),
-            self.mem.len()
-        );
-        Self { gain, ..self }
+}
+impl<U, V> Update for Derivative<U, V> {}
+impl<U, V> Read<Vec<f64>, U> for Derivative<U, V> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        self.rate = match self.previous.as_ref() {
+            Some(previous) => data
+                .iter()
+                .zip(previous)
+                .map(|(x, x0)| (x - x0) * self.sampling_frequency_hz)
+                .collect(),
+            None => vec![0f64; data.len()],
+        };
+        self.previous = Some((**data).clone());
     }
-    /// Sets the integrator zero point
-    pub fn zero(self, zero: Vec<T>) -> Self {
-        Self { zero, ..self }
+}
+impl<U, V> Write<Vec<f64>, V> for Derivative<U, V> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, V>>> {
+        Some(Arc::new(Data::new(self.rate.clone())))
     }
 }
-impl<T, U> Update for Integrator<T, U> {}
-impl<T, U> Read<Vec<T>, U> for Integrator<T, U>
-where
-    T: Copy + Mul<Output = T> + Sub<Output = T> + SubAssign,
-{
+
+/// Concatenates data into a [Vec]
+pub struct Concat<T>(Vec<T>);
+impl<T: Default> Default for Concat<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+impl<T> Update for Concat<T> {}
+impl<T: Clone + Default, U> Read<T, U> for Concat<T> {
+    fn read(&mut self, data: Arc<Data<T, U>>) {
+        self.0.push((*data).clone());
+    }
+}
+impl<T: Clone, U> Write<Vec<T>, U> for Concat<T> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<T>, U>>> {
+        Some(Arc::new(Data::new(take(&mut self.0))))
+    }
+}
+
+/// [ConcatMatrix] error
+#[cfg(feature = "nalgebra")]
+#[derive(Debug, thiserror::Error)]
+pub enum ConcatError {
+    #[error("frame #{frame} width mismatch: expected {expected}, found {found}")]
+    Width {
+        frame: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/**
+Concatenates data frames into a [DMatrix](nalgebra::DMatrix)
+
+Each frame is a row of the matrix of shape (frames x width)
+
+# Example
+```
+use dos_actors::{
+    clients::ConcatMatrix,
+    io::{Data, Read, Write},
+};
+use std::sync::Arc;
+enum Frame {};
+let mut concat = ConcatMatrix::default();
+for i in 0..3 {
+    <ConcatMatrix as Read<Vec<f64>, Frame>>::read(
+        &mut concat,
+        Arc::new(Data::new(vec![i as f64, i as f64 * 10., i as f64 * 100.])),
+    );
+}
+let matrix = <ConcatMatrix as Write<nalgebra::DMatrix<f64>, Frame>>::write(&mut concat).unwrap();
+assert_eq!(matrix.shape(), (3, 3));
+assert_eq!(matrix[(2, 0)], 2.);
+assert_eq!(matrix.row(1).iter().cloned().collect::<Vec<f64>>(), vec![1., 10., 100.]);
+
+<ConcatMatrix as Read<Vec<f64>, Frame>>::read(&mut concat, Arc::new(Data::new(vec![1.; 3])));
+<ConcatMatrix as Read<Vec<f64>, Frame>>::read(&mut concat, Arc::new(Data::new(vec![1.; 2])));
+assert!(concat.matrix().is_err());
+```
+*/
+#[cfg(feature = "nalgebra")]
+#[derive(Debug, Default)]
+pub struct ConcatMatrix {
+    frames: Vec<f64>,
+    width: Option<usize>,
+    n_frame: usize,
+    error: Option<ConcatError>,
+}
+#[cfg(feature = "nalgebra")]
+impl ConcatMatrix {
+    /// Returns the matrix (frames x width) of the concatenated frames and resets the concatenation
+    pub fn matrix(&mut self) -> Result<nalgebra::DMatrix<f64>, ConcatError> {
+        let n_frame = take(&mut self.n_frame);
+        let width = self.width.take().unwrap_or_default();
+        let frames = take(&mut self.frames);
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(nalgebra::DMatrix::from_row_slice(n_frame, width, &frames)),
+        }
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl Update for ConcatMatrix {}
+#[cfg(feature = "nalgebra")]
+impl<U> Read<Vec<f64>, U> for ConcatMatrix {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        let found = data.len();
+        match self.width {
+            Some(expected) if expected != found => {
+                if self.error.is_none() {
+                    self.error = Some(ConcatError::Width {
+                        frame: self.n_frame,
+                        expected,
+                        found,
+                    });
+                }
+            }
+            _ => {
+                self.width = Some(found);
+                self.frames.extend_from_slice(&data);
+            }
+        }
+        self.n_frame += 1;
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Write<nalgebra::DMatrix<f64>, U> for ConcatMatrix {
+    fn write(&mut self) -> Option<Arc<Data<nalgebra::DMatrix<f64>, U>>> {
+        match self.matrix() {
+            Ok(matrix) => Some(Arc::new(Data::new(matrix))),
+            Err(e) => {
+                log::error!("{}", e);
+                None
+            }
+        }
+    }
+}
+
+/**
+Integral controller
+
+The integrator is reset to its zero point when it receives `true` from a [Reset] input,
+the output of the integrator is the zero point at the step the reset is received.
+
+# Example
+An integrator reset by a single pulse
+```
+# tokio_test::block_on(async {
+use dos_actors::{
+    clients::{Flag, Integrator, Reset},
+    prelude::*,
+};
+enum Sig {};
+let mut source: Initiator<_> = Signals::new(1, 10).signals(Signal::Constant(1.)).into();
+let mut reset: Initiator<_> = Flag::<Reset>::pulses(10, &[4]).into();
+let mut integrator: Actor<_> = Integrator::<f64, Sig>::new(1).gain(1.).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut integrator);
+reset.add_output().build::<bool, Reset>().into_input(&mut integrator);
+integrator.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+Model::new(vec![
+    Box::new(source),
+    Box::new(reset),
+    Box::new(integrator),
+    Box::new(sink),
+])
+.check()?
+.run()
+.wait()
+.await?;
+assert_eq!(
+    **logging.lock().await,
+    vec![-1., -2., -3., -4., 0., -1., -2., -3., -4., -5.]
+);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Default)]
+pub struct Integrator<T, U> {
+    gain: Vec<T>,
+    mem: Vec<T>,
+    zero: Vec<T>,
+    uid: PhantomData<U>,
+    schedule: Option<Box<dyn Fn(usize) -> T + Send>>,
+    step: usize,
+    reset: bool,
+}
+impl<T, U> Integrator<T, U>
+where
+    T: Default + Clone,
+{
+    /// Creates a new integral controller
+    pub fn new(n_data: usize) -> Self {
+        Self {
+            gain: vec![Default::default(); n_data],
+            mem: vec![Default::default(); n_data],
+            zero: vec![Default::default(); n_data],
+            uid: PhantomData,
+            schedule: None,
+            step: 0,
+            reset: false,
+        }
+    }
+    /// Sets a unique gain
+    pub fn gain(self, gain: T) -> Self {
+        Self {
+            gain: vec![gain; self.mem.len()],
+            ..self
+        }
+    }
+    /// Sets the gain vector
+    pub fn gain_vector(self, gain: Vec<T>) -> Self {
+        assert_eq!(
+            gain.len(),
+            self.mem.len(),
+            "gain vector length error: expected {} found {}",
+            gain.len(),
+            self.mem.len()
+        );
+        Self { gain, ..self }
+    }
+    /// Sets the integrator zero point
+    pub fn zero(self, zero: Vec<T>) -> Self {
+        Self { zero, ..self }
+    }
+    /**
+    Sets a unique gain that varies with the integrator step
+
+    At each step `k`, the gain is set to `schedule(k)`, the step is incremented each time the integrator reads its input
+
+    # Example
+    A gain ramping from 0 to 1 over 10 steps
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{clients::Integrator, prelude::*};
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 10).signals(Signal::Constant(1.)).into();
+    let mut integrator: Actor<_> = Integrator::<f64, Sig>::new(1)
+        .gain_schedule(|k| (k as f64 / 10.).min(1.))
+        .into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut integrator);
+    integrator.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(integrator), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let correction: Vec<f64> = logging.lock().await.windows(2).map(|y| (y[1] - y[0]).abs()).collect();
+    assert!(correction.windows(2).all(|c| c[1] > c[0]));
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn gain_schedule<F>(self, schedule: F) -> Self
+    where
+        F: Fn(usize) -> T + Send + 'static,
+    {
+        Self {
+            schedule: Some(Box::new(schedule)),
+            ..self
+        }
+    }
+}
+impl<T: Copy, U> Integrator<T, U> {
+    // Updates the gain according to the gain schedule
+    fn schedule_gain(&mut self) {
+        if let Some(schedule) = self.schedule.as_ref() {
+            let gain = schedule(self.step);
+            self.gain.iter_mut().for_each(|g| *g = gain);
+        }
+        self.step += 1;
+    }
+}
+impl<T: Default, U> Update for Integrator<T, U> {
+    fn update(&mut self) {
+        if self.reset {
+            self.mem.iter_mut().for_each(|x| *x = T::default());
+            self.reset = false;
+        }
+    }
+}
+impl<T, U> Read<bool, Reset> for Integrator<T, U> {
+    fn read(&mut self, data: Arc<Data<bool, Reset>>) {
+        self.reset |= **data;
+    }
+}
+impl<T, U> Read<Vec<T>, U> for Integrator<T, U>
+where
+    T: Copy + Mul<Output = T> + Sub<Output = T> + SubAssign,
+{
     fn read(&mut self, data: Arc<Data<Vec<T>, U>>) {
+        self.schedule_gain();
         self.mem
             .iter_mut()
             .zip(&self.gain)
@@ -288,3 +1544,989 @@ where
         Some(Arc::new(Data::new(y)))
     }
 }
+#[cfg(feature = "nalgebra")]
+impl<T, U> Read<nalgebra::DVector<T>, U> for Integrator<T, U>
+where
+    T: nalgebra::Scalar + Copy + Mul<Output = T> + Sub<Output = T> + SubAssign,
+{
+    fn read(&mut self, data: Arc<Data<nalgebra::DVector<T>, U>>) {
+        self.schedule_gain();
+        self.mem
+            .iter_mut()
+            .zip(&self.gain)
+            .zip(&self.zero)
+            .zip(data.iter())
+            .for_each(|(((x, g), z), u)| *x -= *g * (*u - *z));
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T, V, U> Write<nalgebra::DVector<T>, V> for Integrator<T, U>
+where
+    T: nalgebra::Scalar + Copy + Add<Output = T>,
+{
+    fn write(&mut self) -> Option<Arc<Data<nalgebra::DVector<T>, V>>> {
+        let y = nalgebra::DVector::from_iterator(
+            self.mem.len(),
+            self.mem.iter().zip(&self.zero).map(|(m, z)| *m + *z),
+        );
+        Some(Arc::new(Data::new(y)))
+    }
+}
+
+/**
+Linear time-invariant clients interface
+
+The interface gives access to the steady-state properties of linear clients without running a [Model](crate::model::Model)
+*/
+#[cfg(feature = "nalgebra")]
+pub trait LinearSystem {
+    /// Returns the steady-state (DC) gain matrix (outputs x inputs)
+    fn dc_gain(&self) -> nalgebra::DMatrix<f64>;
+    /// Returns the number of states
+    fn n_states(&self) -> usize;
+}
+
+/**
+Matrix gain
+
+The output is the product of the gain matrix with the input
+
+# Example
+```
+use dos_actors::clients::{Gain, LinearSystem};
+use nalgebra::DMatrix;
+let mat = DMatrix::from_row_slice(2, 3, &[1., 2., 3., 4., 5., 6.]);
+let gain = Gain::<()>::new(mat.clone());
+assert_eq!(gain.dc_gain(), mat);
+assert_eq!(gain.n_states(), 0);
+```
+*/
+#[cfg(feature = "nalgebra")]
+pub struct Gain<U> {
+    mat: nalgebra::DMatrix<f64>,
+    y: nalgebra::DVector<f64>,
+    uid: PhantomData<U>,
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Gain<U> {
+    /// Creates a new gain from the gain matrix
+    pub fn new(mat: nalgebra::DMatrix<f64>) -> Self {
+        Self {
+            y: nalgebra::DVector::zeros(mat.nrows()),
+            mat,
+            uid: PhantomData,
+        }
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Update for Gain<U> {}
+#[cfg(feature = "nalgebra")]
+impl<U> Read<Vec<f64>, U> for Gain<U> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        self.y = &self.mat * nalgebra::DVector::from_column_slice(&data);
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U, V> Write<Vec<f64>, V> for Gain<U> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, V>>> {
+        Some(Arc::new(Data::new(self.y.as_slice().to_vec())))
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> LinearSystem for Gain<U> {
+    fn dc_gain(&self) -> nalgebra::DMatrix<f64> {
+        self.mat.clone()
+    }
+    fn n_states(&self) -> usize {
+        0
+    }
+}
+/// The integrator DC gain is infinite, with the sign opposite to the gain, for all non-zero gains
+#[cfg(feature = "nalgebra")]
+impl<U> LinearSystem for Integrator<f64, U> {
+    fn dc_gain(&self) -> nalgebra::DMatrix<f64> {
+        nalgebra::DMatrix::from_diagonal(&nalgebra::DVector::from_iterator(
+            self.gain.len(),
+            self.gain.iter().map(|&g| {
+                if g == 0f64 {
+                    0f64
+                } else {
+                    -g.signum() * f64::INFINITY
+                }
+            }),
+        ))
+    }
+    fn n_states(&self) -> usize {
+        self.mem.len()
+    }
+}
+
+/// Real part UID
+pub enum Real {}
+/// Imaginary part UID
+pub enum Imag {}
+/**
+Splits complex data into real and imaginary parts
+
+# Example
+```
+use dos_actors::{
+    clients::{ComplexJoin, ComplexSplit, Imag, Real},
+    io::{Data, Read, Write},
+};
+use nalgebra::Complex;
+use std::sync::Arc;
+enum Spectrum {};
+let z = vec![Complex::new(1., -1.), Complex::new(0.5, 2.), Complex::new(-3., 0.)];
+let mut split = ComplexSplit::<Spectrum>::default();
+split.read(Arc::new(Data::new(z.clone())));
+let re = <ComplexSplit<Spectrum> as Write<Vec<f64>, Real>>::write(&mut split).unwrap();
+let im = <ComplexSplit<Spectrum> as Write<Vec<f64>, Imag>>::write(&mut split).unwrap();
+assert_eq!(**re, vec![1., 0.5, -3.]);
+assert_eq!(**im, vec![-1., 2., 0.]);
+
+let mut join = ComplexJoin::<Spectrum>::default();
+<ComplexJoin<Spectrum> as Read<Vec<f64>, Real>>::read(&mut join, re);
+<ComplexJoin<Spectrum> as Read<Vec<f64>, Imag>>::read(&mut join, im);
+assert_eq!(**join.write().unwrap(), z);
+
+// parts of different sizes
+<ComplexJoin<Spectrum> as Read<Vec<f64>, Imag>>::read(&mut join, Arc::new(Data::new(vec![1.])));
+assert!(join.write().is_none());
+```
+*/
+#[cfg(feature = "nalgebra")]
+pub struct ComplexSplit<U> {
+    data: Arc<Data<Vec<nalgebra::Complex<f64>>, U>>,
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Default for ComplexSplit<U> {
+    fn default() -> Self {
+        Self {
+            data: Arc::new(Data::new(Vec::new())),
+        }
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Update for ComplexSplit<U> {}
+#[cfg(feature = "nalgebra")]
+impl<U> Read<Vec<nalgebra::Complex<f64>>, U> for ComplexSplit<U> {
+    fn read(&mut self, data: Arc<Data<Vec<nalgebra::Complex<f64>>, U>>) {
+        self.data = data;
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Write<Vec<f64>, Real> for ComplexSplit<U> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, Real>>> {
+        Some(Arc::new(Data::new(
+            self.data.iter().map(|z| z.re).collect(),
+        )))
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Write<Vec<f64>, Imag> for ComplexSplit<U> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, Imag>>> {
+        Some(Arc::new(Data::new(
+            self.data.iter().map(|z| z.im).collect(),
+        )))
+    }
+}
+
+/// Joins real and imaginary parts into complex data
+///
+/// The join stops writing if the real and imaginary parts do not have the same size
+#[cfg(feature = "nalgebra")]
+pub struct ComplexJoin<U> {
+    re: Vec<f64>,
+    im: Vec<f64>,
+    uid: PhantomData<U>,
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Default for ComplexJoin<U> {
+    fn default() -> Self {
+        Self {
+            re: Vec::new(),
+            im: Vec::new(),
+            uid: PhantomData,
+        }
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Update for ComplexJoin<U> {}
+#[cfg(feature = "nalgebra")]
+impl<U> Read<Vec<f64>, Real> for ComplexJoin<U> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, Real>>) {
+        self.re = (**data).clone();
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Read<Vec<f64>, Imag> for ComplexJoin<U> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, Imag>>) {
+        self.im = (**data).clone();
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<U> Write<Vec<nalgebra::Complex<f64>>, U> for ComplexJoin<U> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<nalgebra::Complex<f64>>, U>>> {
+        if self.re.len() != self.im.len() {
+            log::warn!(
+                "real ({}) and imaginary ({}) parts size mismatch",
+                self.re.len(),
+                self.im.len()
+            );
+            return None;
+        }
+        Some(Arc::new(Data::new(
+            self.re
+                .iter()
+                .zip(&self.im)
+                .map(|(&re, &im)| nalgebra::Complex::new(re, im))
+                .collect(),
+        )))
+    }
+}
+
+/**
+Streaming mean removal
+
+The running mean of each channel is estimated with an exponential moving average
+of time constant `time_constant_s` and it is subtracted from the input,
+effectively high-pass filtering the input with a cut-off frequency of `1/(2π time_constant_s)`.
+The running mean is initialized with the first input,
+the client stops writing if the size of an input does not match the size of the running mean.
+
+# Example
+A DC offset removed from a sinusoid
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::MeanRemoval, prelude::*};
+enum Raw {};
+enum HighPassed {};
+let sampling_frequency_hz = 1000f64;
+let mut source: Initiator<_> = Signals::new(1, 5000)
+    .signals(
+        Signal::Constant(5.)
+            + Signal::Sinusoid {
+                amplitude: 1.,
+                sampling_frequency_hz,
+                frequency_hz: 20.,
+                phase_s: 0.,
+            },
+    )
+    .into();
+let mut high_pass: Actor<_> = MeanRemoval::<Raw>::new(0.5, sampling_frequency_hz).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Raw>().into_input(&mut high_pass);
+high_pass
+    .add_output()
+    .build::<Vec<f64>, HighPassed>()
+    .into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(high_pass), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let logs = logging.lock().await;
+let dc = logs[4000..].iter().sum::<f64>() / 1000.;
+assert!(dc.abs() < 1e-2, "DC: {dc}");
+let max = logs[4000..].iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+assert!((max - 1.).abs() < 5e-2, "max: {max}");
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+
+An input which size changes stops the mean removal
+```
+use dos_actors::{clients::MeanRemoval, io::{Data, Read, Write}};
+use std::sync::Arc;
+enum Raw {};
+enum HighPassed {};
+let mut high_pass = MeanRemoval::<Raw>::new(0.5, 1000.);
+high_pass.read(Arc::new(Data::new(vec![1.; 2])));
+assert!(<MeanRemoval<Raw> as Write<Vec<f64>, HighPassed>>::write(&mut high_pass).is_some());
+high_pass.read(Arc::new(Data::new(vec![1.; 3])));
+assert!(<MeanRemoval<Raw> as Write<Vec<f64>, HighPassed>>::write(&mut high_pass).is_none());
+```
+*/
+pub struct MeanRemoval<U> {
+    alpha: f64,
+    mean: Option<Vec<f64>>,
+    y: Option<Vec<f64>>,
+    uid: PhantomData<U>,
+}
+impl<U> MeanRemoval<U> {
+    /// Creates a new mean removal client from the running mean time constant and the sampling frequency
+    pub fn new(time_constant_s: f64, sampling_frequency_hz: f64) -> Self {
+        Self {
+            alpha: 1. - (-1. / (time_constant_s * sampling_frequency_hz)).exp(),
+            mean: None,
+            y: Some(Vec::new()),
+            uid: PhantomData,
+        }
+    }
+    /// Returns the running mean of each channel
+    pub fn mean(&self) -> Option<&[f64]> {
+        self.mean.as_deref()
+    }
+}
+impl<U> Update for MeanRemoval<U> {}
+impl<U> Read<Vec<f64>, U> for MeanRemoval<U> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        let alpha = self.alpha;
+        let mean = self.mean.get_or_insert_with(|| (**data).clone());
+        if data.len() != mean.len() {
+            log::warn!(
+                "data size ({}) do not match the running mean size ({})",
+                data.len(),
+                mean.len()
+            );
+            self.y = None;
+            return;
+        }
+        if let Some(y) = self.y.as_mut() {
+            *y = data
+                .iter()
+                .zip(mean.iter_mut())
+                .map(|(x, m)| {
+                    *m += alpha * (x - *m);
+                    x - *m
+                })
+                .collect();
+        }
+    }
+}
+impl<U, V> Write<Vec<f64>, V> for MeanRemoval<U> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, V>>> {
+        self.y.clone().map(|y| Arc::new(Data::new(y)))
+    }
+}
+
+// Transform of a pipeline
+type Stage = Box<dyn FnMut(&mut Vec<f64>) + Send>;
+/**
+Chain of transforms within a single actor
+
+The stages are applied in order to the input at each [update](Update::update),
+running several cheap transforms within a single actor instead of one actor per transform,
+at the expense of the granularity of the [Model](crate::model::Model) graph.
+
+# Example
+A scale-then-clamp pipeline compared to a scale actor followed by a clamp actor
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Pipeline, prelude::*};
+enum U {};
+enum Scaled {};
+enum Y {};
+let scale = |x: &mut Vec<f64>| x.iter_mut().for_each(|x| *x *= 0.5);
+let clamp = |x: &mut Vec<f64>| x.iter_mut().for_each(|x| *x = x.clamp(0., 2.));
+// single actor
+let mut source: Initiator<_> = Signals::new(1, 10).signals(Signal::Ramp { a: 1., b: -2. }).into();
+let mut pipeline: Actor<_> = Pipeline::<U, Y>::new().stage(scale).stage(clamp).into();
+let single = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(single.clone());
+source.add_output().build::<Vec<f64>, U>().into_input(&mut pipeline);
+pipeline.add_output().build::<Vec<f64>, Y>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(pipeline), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+// two actors
+let mut source: Initiator<_> = Signals::new(1, 10).signals(Signal::Ramp { a: 1., b: -2. }).into();
+let mut scaler: Actor<_> = Pipeline::<U, Scaled>::new().stage(scale).into();
+let mut clamper: Actor<_> = Pipeline::<Scaled, Y>::new().stage(clamp).into();
+let separate = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(separate.clone());
+source.add_output().build::<Vec<f64>, U>().into_input(&mut scaler);
+scaler.add_output().build::<Vec<f64>, Scaled>().into_input(&mut clamper);
+clamper.add_output().build::<Vec<f64>, Y>().into_input(&mut sink);
+Model::new(vec![
+    Box::new(source),
+    Box::new(scaler),
+    Box::new(clamper),
+    Box::new(sink),
+])
+.check()?
+.run()
+.wait()
+.await?;
+assert_eq!(*single.lock().await.as_slice(), *separate.lock().await.as_slice());
+assert_eq!(
+    *single.lock().await.as_slice(),
+    [0., 0., 0., 0.5, 1., 1.5, 2., 2., 2., 2.]
+);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub struct Pipeline<U, V = U> {
+    stages: Vec<Stage>,
+    data: Vec<f64>,
+    uid: PhantomData<U>,
+    output: PhantomData<V>,
+}
+impl<U, V> Default for Pipeline<U, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<U, V> Pipeline<U, V> {
+    /// Creates a new empty pipeline, the input is passed through unchanged
+    pub fn new() -> Self {
+        Self {
+            stages: Vec::new(),
+            data: Vec::new(),
+            uid: PhantomData,
+            output: PhantomData,
+        }
+    }
+    /// Appends the transform `f` to the pipeline
+    pub fn stage<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&mut Vec<f64>) + Send + 'static,
+    {
+        self.stages.push(Box::new(f));
+        self
+    }
+    /// Returns the # of stages
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+    /// Checks if the pipeline has no stages
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+impl<U, V> Update for Pipeline<U, V> {
+    fn update(&mut self) {
+        for stage in self.stages.iter_mut() {
+            stage(&mut self.data);
+        }
+    }
+}
+impl<U, V> Read<Vec<f64>, U> for Pipeline<U, V> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        self.data = (**data).clone();
+    }
+}
+impl<U, V> Write<Vec<f64>, V> for Pipeline<U, V> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, V>>> {
+        Some(Arc::new(Data::new(self.data.clone())))
+    }
+}
+
+/**
+Data type alias
+
+Re-tags the input `Data<Vec<f64>, U>` as the output `Data<Vec<f64>, V>`, leaving the data unchanged.
+It connects actors exchanging the same data under different types
+without implementing new [Read] and [Write] traits.
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Alias, prelude::*};
+enum Command {};
+enum Setpoint {};
+let mut source: Initiator<_> = Signals::new(1, 10).signals(Signal::Constant(3.)).into();
+let mut alias: Actor<_> = Alias::<Command, Setpoint>::default().into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Command>().into_input(&mut alias);
+alias.add_output().build::<Vec<f64>, Setpoint>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(alias), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(*logging.lock().await.as_slice(), [3f64; 10]);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub struct Alias<U, V> {
+    data: Option<Arc<Data<Vec<f64>, U>>>,
+    uid: PhantomData<V>,
+}
+impl<U, V> Default for Alias<U, V> {
+    fn default() -> Self {
+        Self {
+            data: None,
+            uid: PhantomData,
+        }
+    }
+}
+impl<U, V> Update for Alias<U, V> {}
+impl<U, V> Read<Vec<f64>, U> for Alias<U, V> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        self.data = Some(data);
+    }
+}
+impl<U, V> Write<Vec<f64>, V> for Alias<U, V> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, V>>> {
+        self.data.as_ref().map(|data| {
+            let alias = Data::new((***data).clone());
+            Arc::new(match data.step() {
+                Some(step) => alias.stamped(step),
+                None => alias,
+            })
+        })
+    }
+}
+
+// Closure generating the data of a [Source] at a given step
+type SourceFn = Box<dyn FnMut(usize) -> Option<Vec<f64>> + Send>;
+/**
+Closure based data source
+
+The closure is called with the step index at each update of the client
+and the source ends when the closure returns [None].
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Source, prelude::*};
+enum Sig {};
+let mut source: Initiator<_> = Source::<Sig>::new(|step| (step < 10).then(|| vec![step as f64])).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(
+    *logging.lock().await.as_slice(),
+    (0..10).map(|x| x as f64).collect::<Vec<f64>>()
+);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub struct Source<V> {
+    f: SourceFn,
+    step: usize,
+    data: Option<Vec<f64>>,
+    uid: PhantomData<V>,
+}
+impl<V> Source<V> {
+    /// Creates a new source from a closure of the step index
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnMut(usize) -> Option<Vec<f64>> + Send + 'static,
+    {
+        Self {
+            f: Box::new(f),
+            step: 0,
+            data: None,
+            uid: PhantomData,
+        }
+    }
+}
+impl<V> Update for Source<V> {
+    fn update(&mut self) {
+        self.data = (self.f)(self.step);
+        self.step += 1;
+    }
+}
+impl<V> Write<Vec<f64>, V> for Source<V> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, V>>> {
+        self.data.clone().map(|data| Arc::new(Data::new(data)))
+    }
+}
+
+// Closure consuming the data of a [Sink] at a given step
+type SinkFn = Box<dyn FnMut(usize, &Vec<f64>) + Send>;
+/**
+Closure based data sink
+
+The closure is called with the step index and the data each time the client receives some data.
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Sink, prelude::*};
+use std::sync::{Arc, Mutex};
+enum Sig {};
+let mut source: Initiator<_> = Signals::new(1, 5).signals(Signal::Ramp { a: 2., b: 1. }).into();
+let samples = Arc::new(Mutex::new(Vec::new()));
+let captured = samples.clone();
+let mut sink: Terminator<_> = Sink::<Sig>::new(move |step, data: &Vec<f64>| {
+    assert_eq!(data.len(), 1);
+    captured.lock().unwrap().push((step, data[0]));
+})
+.into();
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(
+    *samples.lock().unwrap(),
+    vec![(0, 1.), (1, 3.), (2, 5.), (3, 7.), (4, 9.)]
+);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub struct Sink<U> {
+    f: SinkFn,
+    step: usize,
+    uid: PhantomData<U>,
+}
+impl<U> Sink<U> {
+    /// Creates a new sink from a closure of the step index and of the data
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnMut(usize, &Vec<f64>) + Send + 'static,
+    {
+        Self {
+            f: Box::new(f),
+            step: 0,
+            uid: PhantomData,
+        }
+    }
+}
+impl<U> Update for Sink<U> {}
+impl<U> Read<Vec<f64>, U> for Sink<U> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        (self.f)(self.step, &data);
+        self.step += 1;
+    }
+}
+
+/**
+Histogram of the input values
+
+The values are counted into the bins defined by the bin edges `[e_0, e_1, ..., e_n]`,
+the bin #`i` counts the values within `[e_i, e_i+1[`.
+The values lower than `e_0` are counted into the underflow bin
+and the values greater than or equal to `e_n` are counted into the overflow bin.
+All the entries of the input are counted into the same histogram and NaN values are ignored.
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Histogram, prelude::*};
+enum Sig {};
+// uniform distribution in [-100, 899]
+let mut source: Initiator<_> = Signals::new(1, 1000)
+    .signals(Signal::Ramp { a: 1., b: -100. })
+    .into();
+let histogram = Histogram::<Sig>::uniform(0., 800., 8).into_arcx();
+let mut sink = Terminator::<_>::new(histogram.clone());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let histogram = histogram.lock().await;
+assert_eq!(histogram.counts(), &[100; 8]);
+assert_eq!((histogram.underflow(), histogram.overflow()), (100, 100));
+assert_eq!(histogram.total(), 1000);
+let median = histogram.quantile(0.5).unwrap();
+assert!((300. ..=400.).contains(&median), "median: {median}");
+assert!(histogram.quantile(0.05).is_none());
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct Histogram<U> {
+    edges: Vec<f64>,
+    counts: Vec<usize>,
+    underflow: usize,
+    overflow: usize,
+    uid: PhantomData<U>,
+}
+impl<U> Histogram<U> {
+    /// Creates a new histogram from the bin edges sorted in increasing order
+    pub fn new(edges: Vec<f64>) -> Self {
+        assert!(edges.len() > 1, "a histogram requires at least 2 bin edges");
+        assert!(
+            edges.windows(2).all(|e| e[0] < e[1]),
+            "the histogram bin edges must be strictly increasing"
+        );
+        Self {
+            counts: vec![0; edges.len() - 1],
+            edges,
+            underflow: 0,
+            overflow: 0,
+            uid: PhantomData,
+        }
+    }
+    /// Creates a new histogram with `n_bin` bins of equal width within `[lower, upper[`
+    pub fn uniform(lower: f64, upper: f64, n_bin: usize) -> Self {
+        let width = (upper - lower) / n_bin as f64;
+        Self::new((0..=n_bin).map(|i| lower + i as f64 * width).collect())
+    }
+    /// Returns the bin edges
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+    /// Returns the counts of each bin
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+    /// Returns the number of values lower than the first edge
+    pub fn underflow(&self) -> usize {
+        self.underflow
+    }
+    /// Returns the number of values greater than or equal to the last edge
+    pub fn overflow(&self) -> usize {
+        self.overflow
+    }
+    /// Returns the total number of values, including the underflow and the overflow
+    pub fn total(&self) -> usize {
+        self.underflow + self.counts.iter().sum::<usize>() + self.overflow
+    }
+    /**
+    Returns the quantile `p` in `[0,1]`
+
+    The quantile is linearly interpolated within the bin it belongs to.
+    Returns [None] if the histogram is empty or if the quantile falls into either the underflow or the overflow bin.
+    */
+    pub fn quantile(&self, p: f64) -> Option<f64> {
+        assert!(
+            (0. ..=1.).contains(&p),
+            "the quantile must be within [0,1], found {p}"
+        );
+        let rank = p * self.total() as f64;
+        let mut cumsum = self.underflow as f64;
+        if self.total() == 0 || rank < cumsum {
+            return None;
+        }
+        for (&count, edge) in self.counts.iter().zip(self.edges.windows(2)) {
+            let count = count as f64;
+            if count > 0. && rank <= cumsum + count {
+                return Some(edge[0] + (rank - cumsum) * (edge[1] - edge[0]) / count);
+            }
+            cumsum += count;
+        }
+        None
+    }
+}
+impl<U> Update for Histogram<U> {}
+impl<U> Read<Vec<f64>, U> for Histogram<U> {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        for value in data.iter().filter(|value| !value.is_nan()) {
+            match self.edges.partition_point(|edge| edge <= value) {
+                0 => self.underflow += 1,
+                i if i == self.edges.len() => self.overflow += 1,
+                i => self.counts[i - 1] += 1,
+            }
+        }
+    }
+}
+
+/// Reset flag UID
+pub enum Reset {}
+/// Flag schedule
+type FlagFn = Box<dyn Fn(usize) -> bool + Send>;
+/**
+Boolean event source
+
+The flag is set to `schedule(k)` at step `k` for `n_step` steps.
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Flag, prelude::*};
+enum Event {};
+let mut source: Initiator<_> = Flag::<Event>::new(6, |k| k % 3 == 0).into();
+let logging = Logging::<bool>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<bool, Event>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(
+    **logging.lock().await,
+    vec![true, false, false, true, false, false]
+);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub struct Flag<U> {
+    schedule: FlagFn,
+    step: usize,
+    n_step: usize,
+    uid: PhantomData<U>,
+}
+impl<U> Flag<U> {
+    /// Creates a new flag source from the flag schedule
+    pub fn new<F>(n_step: usize, schedule: F) -> Self
+    where
+        F: Fn(usize) -> bool + Send + 'static,
+    {
+        Self {
+            schedule: Box::new(schedule),
+            step: 0,
+            n_step,
+            uid: PhantomData,
+        }
+    }
+    /// Creates a new flag source that is set only at the given `steps`
+    pub fn pulses(n_step: usize, steps: &[usize]) -> Self {
+        let steps = steps.to_vec();
+        Self::new(n_step, move |k| steps.contains(&k))
+    }
+}
+impl<U> Update for Flag<U> {}
+impl<U> Write<bool, U> for Flag<U> {
+    fn write(&mut self) -> Option<Arc<Data<bool, U>>> {
+        if self.step < self.n_step {
+            let flag = (self.schedule)(self.step);
+            self.step += 1;
+            Some(Arc::new(Data::new(flag)))
+        } else {
+            None
+        }
+    }
+}
+
+// A channel monitored by [Settled]
+#[derive(Debug)]
+struct SettledChannel {
+    id: TypeId,
+    name: String,
+    band: Vec<f64>,
+    since: usize,
+    count: usize,
+    step: usize,
+}
+/**
+Steady-state monitor
+
+A channel has settled once all its values have stayed within `tolerance` of the values
+at the beginning of the band for `n_step` consecutive samples.
+The [flag](Settled::flag) is set when all the channels registered with [Settled::channel] have settled
+and [Settled::settling_step] returns the step at which the last channel entered its tolerance band.
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{clients::Settled, prelude::*};
+use std::sync::atomic::Ordering;
+enum Fast {};
+enum Slow {};
+let step_at = |k: usize| Signal::Sequence((0..100).map(|i| i.min(k) as f64).collect());
+let mut fast: Initiator<_> = Signals::new(2, 100).signals(step_at(20)).into();
+let mut slow: Initiator<_> = Signals::new(1, 100).signals(step_at(50)).into();
+let settled = Settled::new(1e-6, 10).channel::<Fast>().channel::<Slow>();
+let flag = settled.flag();
+let settled = settled.into_arcx();
+let mut sink = Terminator::<_>::new(settled.clone());
+fast.add_output().build::<Vec<f64>, Fast>().into_input(&mut sink);
+slow.add_output().build::<Vec<f64>, Slow>().into_input(&mut sink);
+Model::new(vec![Box::new(fast), Box::new(slow), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let settled = settled.lock().await;
+assert!(flag.load(Ordering::Relaxed));
+assert_eq!(settled.channel_settling_step::<Fast>(), Some(20));
+assert_eq!(settled.channel_settling_step::<Slow>(), Some(50));
+assert_eq!(settled.settling_step(), Some(50));
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct Settled {
+    tolerance: f64,
+    n_step: usize,
+    channels: Vec<SettledChannel>,
+    flag: Arc<AtomicBool>,
+}
+impl Settled {
+    /// Creates a new monitor for channels staying within `tolerance` for `n_step` samples
+    pub fn new(tolerance: f64, n_step: usize) -> Self {
+        assert!(
+            n_step > 0,
+            "the number of settled steps must be greater than 0"
+        );
+        Self {
+            tolerance,
+            n_step,
+            channels: Vec::new(),
+            flag: Arc::new(AtomicBool::new(false)),
+        }
+    }
+    /// Adds the input `U` to the monitored channels
+    pub fn channel<U: 'static>(self) -> Self {
+        let mut channels = self.channels;
+        channels.push(SettledChannel {
+            id: TypeId::of::<U>(),
+            name: type_name::<U>().to_string(),
+            band: Vec::new(),
+            since: 0,
+            count: 0,
+            step: 0,
+        });
+        Self { channels, ..self }
+    }
+    /// Returns the flag that is set while all the channels have settled
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        self.flag.clone()
+    }
+    /// Checks if all the channels have settled
+    pub fn is_settled(&self) -> bool {
+        !self.channels.is_empty() && self.channels.iter().all(|c| c.count >= self.n_step)
+    }
+    /// Returns the step at which the channel `U` entered its tolerance band if it has settled
+    pub fn channel_settling_step<U: 'static>(&self) -> Option<usize> {
+        self.channels
+            .iter()
+            .find(|c| c.id == TypeId::of::<U>() && c.count >= self.n_step)
+            .map(|c| c.since)
+    }
+    /// Returns the latest settling step of all the channels if they have all settled
+    pub fn settling_step(&self) -> Option<usize> {
+        if self.is_settled() {
+            self.channels.iter().map(|c| c.since).max()
+        } else {
+            None
+        }
+    }
+}
+impl Update for Settled {}
+impl<U: 'static> Read<Vec<f64>, U> for Settled {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, U>>) {
+        let tolerance = self.tolerance;
+        match self.channels.iter_mut().find(|c| c.id == TypeId::of::<U>()) {
+            Some(c) => {
+                if c.band.len() == data.len()
+                    && c.band
+                        .iter()
+                        .zip(data.iter())
+                        .all(|(b, x)| (x - b).abs() <= tolerance)
+                {
+                    c.count += 1;
+                    if c.count == self.n_step {
+                        log::info!("{} settled from step {}", c.name, c.since);
+                    }
+                } else {
+                    c.band = (*data).clone();
+                    c.since = c.step;
+                    c.count = 1;
+                }
+                c.step += 1;
+            }
+            None => {
+                log::warn!("{} is not monitored", type_name::<U>());
+                return;
+            }
+        }
+        self.flag.store(self.is_settled(), Ordering::Relaxed);
+    }
+}
@@ -24,6 +24,10 @@ use crate::{
 };
 use mount_ctrl::controller;
 use mount_ctrl::drives;
+#[cfg(feature = "noise")]
+use rand::{rngs::StdRng, SeedableRng};
+#[cfg(feature = "noise")]
+use rand_distr::{Distribution, Normal, NormalError};
 use std::{ptr, sync::Arc};
 
 pub struct Mount<'a> {
@@ -64,6 +68,93 @@ impl<'a> Read<Vec<f64>, MountEncoders> for Mount<'a> {
         }
     }
 }
+
+/**
+Mount encoders model
+
+Adds Gaussian noise (with the `noise` feature) and quantization to the [MountEncoders] data,
+the noise is added first and the result is snapped to the encoder resolution grid.
+The client sits between the FEM and the [Mount] actors.
+
+# Example
+```
+use dos_actors::{
+    clients::mount::{EncodersModel, MountEncoders},
+    io::{Data, Read, Write},
+    Update,
+};
+use std::sync::Arc;
+let lsb = 1e-3;
+let mut encoders = EncodersModel::new().quantization(lsb);
+encoders.read(Arc::new(Data::new(vec![0.12345, -0.0456, 1.0004])));
+encoders.update();
+let data = <EncodersModel as Write<Vec<f64>, MountEncoders>>::write(&mut encoders).unwrap();
+assert!(data
+    .iter()
+    .all(|x| ((x / lsb) - (x / lsb).round()).abs() < 1e-9));
+data.iter()
+    .zip(vec![0.123, -0.046, 1.0])
+    .for_each(|(x, y)| assert!((x - y).abs() < 1e-12));
+```
+*/
+#[derive(Default)]
+pub struct EncodersModel {
+    lsb: Option<f64>,
+    #[cfg(feature = "noise")]
+    noise: Option<(Normal<f64>, StdRng)>,
+    data: Vec<f64>,
+}
+impl EncodersModel {
+    /// Creates a noiseless encoders model with infinite resolution
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Sets the encoders resolution (least significant bit)
+    pub fn quantization(self, lsb: f64) -> Self {
+        Self {
+            lsb: Some(lsb),
+            ..self
+        }
+    }
+    /// Adds zero mean Gaussian noise of standard deviation `sigma`, the noise generator is seeded with `seed`
+    #[cfg(feature = "noise")]
+    pub fn gaussian_noise(self, sigma: f64, seed: u64) -> Result<Self, NormalError> {
+        Ok(Self {
+            noise: Some((Normal::new(0f64, sigma)?, StdRng::seed_from_u64(seed))),
+            ..self
+        })
+    }
+}
+impl Update for EncodersModel {
+    #[cfg(feature = "noise")]
+    fn seed(&mut self, seed: u64) {
+        if let Some((_, rng)) = self.noise.as_mut() {
+            *rng = StdRng::seed_from_u64(seed);
+        }
+    }
+    fn update(&mut self) {
+        #[cfg(feature = "noise")]
+        if let Some((noise, rng)) = self.noise.as_mut() {
+            self.data.iter_mut().for_each(|x| *x += noise.sample(rng));
+        }
+        if let Some(lsb) = self.lsb {
+            self.data
+                .iter_mut()
+                .for_each(|x| *x = (*x / lsb).round() * lsb);
+        }
+    }
+}
+impl Read<Vec<f64>, MountEncoders> for EncodersModel {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, MountEncoders>>) {
+        self.data = (**data).clone();
+    }
+}
+impl Write<Vec<f64>, MountEncoders> for EncodersModel {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, MountEncoders>>> {
+        Some(Arc::new(Data::new(self.data.clone())))
+    }
+}
+
 pub enum MountSetPoint {}
 impl<'a> Read<Vec<f64>, MountSetPoint> for Mount<'a> {
     fn read(&mut self, data: Arc<Data<Vec<f64>, MountSetPoint>>) {
@@ -1,14 +1,27 @@
+use super::Logging;
 use crate::{
     io::{Data, Write},
     Update,
 };
-use std::{ops::Add, sync::Arc};
+use std::{
+    fmt,
+    ops::Add,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
 
+#[cfg(feature = "noise")]
+use rand::{rngs::StdRng, Rng, SeedableRng};
 #[cfg(feature = "noise")]
 use rand_distr::{Distribution, Normal, NormalError};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Signal types
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Signal {
     /// A constant signal
     Constant(f64),
@@ -23,9 +36,11 @@ pub enum Signal {
     Ramp { a: f64, b: f64 },
     /// White noise
     #[cfg(feature = "noise")]
-    WhiteNoise(Normal<f64>),
+    WhiteNoise(#[cfg_attr(feature = "serde", serde(with = "normal"))] Normal<f64>),
     /// A simphony?
     Composite(Vec<Signal>),
+    /// A sequence of samples, zero after the last sample
+    Sequence(Vec<f64>),
 }
 
 #[cfg(feature = "noise")]
@@ -50,8 +65,156 @@ impl Signal {
             Ok(self)
         }
     }
+    /// Returns the signal value at step `i`, the white noise is sampled with `rng`
+    pub fn get_with<R: Rng>(&self, i: usize, rng: &mut R) -> f64 {
+        match self {
+            Signal::WhiteNoise(noise) => noise.sample(rng),
+            Signal::Composite(signals) => {
+                signals.iter().map(|signal| signal.get_with(i, rng)).sum()
+            }
+            _ => self.get(i),
+        }
+    }
+    /**
+    Creates a random-phase sequence of `n_step` samples with the one-sided power spectral density `psd`
+
+    The PSD is linearly interpolated at the frequencies `freqs` (in Hz) and it is zero outside of the range of `freqs`.
+    The sequence is the inverse Fourier transform of the square root of the PSD with uniformly distributed random phases,
+    generated with the sampling frequency `fs` and the random number generator seed `seed`.
+    The DC and Nyquist components are set to zero.
+    */
+    pub fn from_psd(freqs: &[f64], psd: &[f64], n_step: usize, fs: f64, seed: u64) -> Self {
+        assert_eq!(
+            freqs.len(),
+            psd.len(),
+            "the frequencies and the PSD must have the same length"
+        );
+        if n_step == 0 {
+            return Signal::Sequence(Vec::new());
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let n = n_step.next_power_of_two();
+        let df = fs / n as f64;
+        let mut spectrum = vec![(0f64, 0f64); n];
+        for k in 1..n / 2 {
+            // amplitude of the sinusoid with the variance of the PSD over the frequency bin
+            let amplitude = (2. * interpolate(freqs, psd, k as f64 * df) * df).sqrt();
+            let phase = rng.gen_range(0f64..2. * std::f64::consts::PI);
+            let (sin, cos) = phase.sin_cos();
+            let half_n = 0.5 * n as f64;
+            spectrum[k] = (half_n * amplitude * cos, half_n * amplitude * sin);
+            spectrum[n - k] = (half_n * amplitude * cos, -half_n * amplitude * sin);
+        }
+        inverse_fft(&mut spectrum);
+        Signal::Sequence(
+            spectrum
+                .into_iter()
+                .take(n_step)
+                .map(|(re, _)| re)
+                .collect(),
+        )
+    }
 }
+// Linear interpolation of (`x`,`y`) at `x0`, zero outside of the range of `x`
+#[cfg(feature = "noise")]
+fn interpolate(x: &[f64], y: &[f64], x0: f64) -> f64 {
+    match x.iter().position(|&x| x >= x0) {
+        Some(0) if x[0] == x0 => y[0],
+        Some(0) | None => 0f64,
+        Some(i) => y[i - 1] + (y[i] - y[i - 1]) * (x0 - x[i - 1]) / (x[i] - x[i - 1]),
+    }
+}
+// In-place inverse discrete Fourier transform of `x`, normalized by the length of `x` that must be a power of 2
+#[cfg(feature = "noise")]
+fn inverse_fft(x: &mut [(f64, f64)]) {
+    let n = x.len();
+    // bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+    // butterflies
+    let mut len = 2;
+    while len <= n {
+        let angle = 2. * std::f64::consts::PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let (a, b) = (x[start + k], x[start + k + len / 2]);
+                let b = (b.0 * cos - b.1 * sin, b.0 * sin + b.1 * cos);
+                x[start + k] = (a.0 + b.0, a.1 + b.1);
+                x[start + k + len / 2] = (a.0 - b.0, a.1 - b.1);
+            }
+        }
+        len <<= 1;
+    }
+    let n = n as f64;
+    x.iter_mut().for_each(|(re, im)| {
+        *re /= n;
+        *im /= n;
+    });
+}
+// (De)serializes a [Normal] distribution as its mean and standard deviation
+#[cfg(all(feature = "noise", feature = "serde"))]
+mod normal {
+    use rand_distr::Normal;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    #[derive(Serialize, Deserialize)]
+    struct Moments {
+        mean: f64,
+        std_dev: f64,
+    }
+    pub fn serialize<S: Serializer>(
+        normal: &Normal<f64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Moments {
+            mean: normal.mean(),
+            std_dev: normal.std_dev(),
+        }
+        .serialize(serializer)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Normal<f64>, D::Error> {
+        let Moments { mean, std_dev } = Moments::deserialize(deserializer)?;
+        Normal::new(mean, std_dev).map_err(D::Error::custom)
+    }
+}
+
 impl Signal {
+    /// Adds `phase_s` to the phase of the sinusoidal signals
+    pub fn phase_offset(self, phase_s: f64) -> Self {
+        use Signal::*;
+        match self {
+            Sinusoid {
+                amplitude,
+                sampling_frequency_hz,
+                frequency_hz,
+                phase_s: phase,
+            } => Sinusoid {
+                amplitude,
+                sampling_frequency_hz,
+                frequency_hz,
+                phase_s: phase + phase_s,
+            },
+            Composite(signals) => Composite(
+                signals
+                    .into_iter()
+                    .map(|signal| signal.phase_offset(phase_s))
+                    .collect(),
+            ),
+            _ => self,
+        }
+    }
     /// Returns the signal value at step `i`
     pub fn get(&self, i: usize) -> f64 {
         use Signal::*;
@@ -73,17 +236,44 @@ impl Signal {
             #[cfg(feature = "noise")]
             WhiteNoise(noise) => noise.sample(&mut rand::thread_rng()),
             Composite(signals) => signals.iter().map(|signal| signal.get(i)).sum(),
+            Sequence(samples) => samples.get(i).cloned().unwrap_or_default(),
         }
     }
 }
 
+type SignalFn = Box<dyn FnMut(usize) -> f64 + Send>;
+// A signal given by a closure of the step index, the clones of a signal share the closure
+#[derive(Clone)]
+struct Callback(Arc<Mutex<SignalFn>>);
+impl Callback {
+    fn get(&self, i: usize) -> f64 {
+        (*self.0.lock().unwrap())(i)
+    }
+}
+impl fmt::Debug for Callback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Callback")
+    }
+}
+
 /// Signals generator
+///
+/// With the `serde` feature, the definition of the signals can be saved to and loaded from JSON with `Signals::to_json` and `Signals::from_json`,
+/// the [stop flag](Signals::until), the [callback signals](Signals::output_fn) and the white noise random number generator are not serialized
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Signals {
     size: usize,
     signals: Vec<Signal>,
     pub step: usize,
     pub n_step: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    until: Option<Arc<AtomicBool>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    callbacks: Vec<(usize, Callback)>,
+    #[cfg(feature = "noise")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    rng: Option<StdRng>,
 }
 impl Signals {
     /// Create `n` null [Signal::Constant]s valid for `n_step` iterations
@@ -94,19 +284,310 @@ impl Signals {
             signals,
             step: 0,
             n_step,
+            until: None,
+            callbacks: Vec::new(),
+            #[cfg(feature = "noise")]
+            rng: None,
+        }
+    }
+    /**
+    Replays the entry #`entry` of a [Logging]
+
+    The entries of the [Logging] must have the same size and
+    the number of steps is set to the number of time samples in the [Logging].
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 100)
+        .signals(Signal::Sinusoid {
+            amplitude: 1f64,
+            sampling_frequency_hz: 100f64,
+            frequency_hz: 5f64,
+            phase_s: 0f64,
+        })
+        .into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+
+    let mut replay: Initiator<_> = Signals::from_logging(&*logging.lock().await, 0).into();
+    let replay_logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(replay_logging.clone());
+    replay.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(replay), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert_eq!(**logging.lock().await, **replay_logging.lock().await);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn from_logging(logging: &Logging<f64>, entry: usize) -> Self {
+        assert!(
+            entry < logging.n_entry,
+            "entry #{} is out of range, the logging has {} entries",
+            entry,
+            logging.n_entry
+        );
+        let n_step = logging.len();
+        if n_step == 0 {
+            return Self::new(0, 0);
+        }
+        let size = logging.n_data() / logging.n_entry;
+        let signals: Vec<_> = (0..size)
+            .map(|k| {
+                Signal::Sequence(
+                    logging
+                        .chunks()
+                        .map(|data| data[entry * size + k])
+                        .collect(),
+                )
+            })
+            .collect();
+        Self {
+            size,
+            signals,
+            step: 0,
+            n_step,
+            until: None,
+            callbacks: Vec::new(),
+            #[cfg(feature = "noise")]
+            rng: None,
+        }
+    }
+    /**
+    Stops the signals generator as soon as `flag` is set to `true`
+
+    `n_step` is then the maximum number of steps.
+
+    # Example
+    The signals stop once a monitor finds the standard deviation of the signal less than a threshold
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{io, prelude::*, Update};
+    use std::{
+        collections::VecDeque,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+    struct Monitor {
+        settled: Arc<AtomicBool>,
+        samples: VecDeque<f64>,
+        n_sample: usize,
+    }
+    impl Update for Monitor {}
+    impl<U> io::Read<Vec<f64>, U> for Monitor {
+        fn read(&mut self, data: Arc<io::Data<Vec<f64>, U>>) {
+            self.n_sample += 1;
+            self.samples.push_back(data[0]);
+            if self.samples.len() > 100 {
+                self.samples.pop_front();
+                let n = self.samples.len() as f64;
+                let mean = self.samples.iter().sum::<f64>() / n;
+                let var = self.samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                if var.sqrt() < 1e-3 {
+                    self.settled.store(true, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+    enum Sig {};
+    let n_step = 100_000;
+    let settled = Arc::new(AtomicBool::new(false));
+    let mut source: Initiator<_> = Signals::new(1, n_step)
+        .signals(Signal::Sequence(
+            (0..n_step)
+                .map(|i| (-(i as f64) / 100.).exp() * (i as f64 / 10.).sin())
+                .collect(),
+        ))
+        .until(settled.clone())
+        .into();
+    let monitor = Monitor {
+        settled: settled.clone(),
+        samples: VecDeque::new(),
+        n_sample: 0,
+    }
+    .into_arcx();
+    let mut sink = Terminator::<_>::new(monitor.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert!(settled.load(Ordering::Relaxed));
+    assert!(monitor.lock().await.n_sample < 1_000);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn until(self, flag: Arc<AtomicBool>) -> Self {
+        Self {
+            until: Some(flag),
+            ..self
         }
     }
+    /**
+    Serializes the signals definition into JSON
+
+    The white noise signals are saved with their mean and standard deviation only,
+    so only the deterministic signals are reproduced identically.
+
+    # Example
+    ```
+    use dos_actors::{io::Write, prelude::*};
+    enum Sig {};
+    let signals = Signals::new(3, 100)
+        .output_signal(
+            0,
+            Signal::Sinusoid {
+                amplitude: 1f64,
+                sampling_frequency_hz: 100f64,
+                frequency_hz: 5f64,
+                phase_s: 0.25,
+            } + Signal::Constant(0.5),
+        )
+        .output_signal(1, Signal::Ramp { a: 0.1, b: -1. })
+        .output_signal(2, Signal::Sequence(vec![1., 2., 3.]));
+    let json = signals.to_json()?;
+    let replay = Signals::from_json(&json)?;
+    let samples = |mut signals: Signals| {
+        std::iter::from_fn(move || <Signals as Write<Vec<f64>, Sig>>::write(&mut signals))
+            .map(|data| (*data).clone())
+            .collect::<Vec<Vec<f64>>>()
+    };
+    assert_eq!(samples(signals), samples(replay));
+    # Ok::<(), serde_json::Error>(())
+    ```
+    */
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+    /// Deserializes the signals definition from JSON
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
     /// Sets the same [Signal] for all outputs
     pub fn signals(self, signal: Signal) -> Self {
         let signals = vec![signal.clone(); self.size];
         Self { signals, ..self }
     }
+    /**
+    Sets the same [Signal] for all outputs with a phase offset of `k` times `per_channel_phase_s` for output #`k`
+
+    # Example
+    ```
+    use dos_actors::{io::Write, prelude::*};
+    enum Sig {};
+    let mut signals = Signals::new(7, 100).stagger(
+        Signal::Sinusoid {
+            amplitude: 1f64,
+            sampling_frequency_hz: 1000f64,
+            frequency_hz: 10f64,
+            phase_s: 0f64,
+        },
+        0.1,
+    );
+    let data = <Signals as Write<Vec<f64>, Sig>>::write(&mut signals).unwrap();
+    for (k, value) in data.iter().enumerate() {
+        let expected = (2f64 * std::f64::consts::PI * 0.1 * k as f64).sin();
+        assert!((value - expected).abs() < 1e-12);
+    }
+    ```
+    */
+    pub fn stagger(self, signal: Signal, per_channel_phase_s: f64) -> Self {
+        let signals = (0..self.size)
+            .map(|k| signal.clone().phase_offset(k as f64 * per_channel_phase_s))
+            .collect();
+        Self { signals, ..self }
+    }
+    /**
+    Creates a single output random-phase signal with the one-sided power spectral density `psd` sampled at `freqs`
+
+    See [Signal::from_psd], the signals of other outputs are set with [Signal::from_psd] and [Signals::output_signal].
+
+    # Example
+    The periodogram of a signal generated from a flat PSD is flat
+    ```
+    use dos_actors::{io::Write, prelude::*};
+    enum Sig {};
+    let (n_step, fs, psd) = (1024, 100f64, 1e-2);
+    let mut signals = Signals::from_psd(&[0., fs / 2.], &[psd, psd], n_step, fs, 42);
+    let x: Vec<f64> = (0..n_step)
+        .map(|_| <Signals as Write<Vec<f64>, Sig>>::write(&mut signals).unwrap()[0])
+        .collect();
+    for k in 1..n_step / 2 {
+        let (re, im) = x.iter().enumerate().fold((0f64, 0f64), |(re, im), (i, x)| {
+            let phase = 2. * std::f64::consts::PI * (k * i) as f64 / n_step as f64;
+            (re + x * phase.cos(), im - x * phase.sin())
+        });
+        let periodogram = 2. * (re * re + im * im) / (fs * n_step as f64);
+        assert!((periodogram / psd - 1.).abs() < 1e-6, "bin #{k}: {periodogram}");
+    }
+    ```
+    */
+    #[cfg(feature = "noise")]
+    pub fn from_psd(freqs: &[f64], psd: &[f64], n_step: usize, fs: f64, seed: u64) -> Self {
+        Self::new(1, n_step).signals(Signal::from_psd(freqs, psd, n_step, fs, seed))
+    }
     /// Sets the [Signal] of output #`k`
     pub fn output_signal(self, k: usize, output_signal: Signal) -> Self {
         let mut signals = self.signals;
         signals[k] = output_signal;
         Self { signals, ..self }
     }
+    /**
+    Adds the value returned by the closure `f` of the step index to the [Signal] of output #`k`
+
+    The closure is not serialized and the clones of the [Signals] share the closure.
+
+    # Example
+    ```
+    use dos_actors::{io::Write, prelude::*};
+    enum Sig {};
+    let mut signals = Signals::new(2, 5)
+        .output_signal(1, Signal::Constant(1.))
+        .output_fn(0, |i| (i * i) as f64)
+        .output_fn(1, |i| (i * i) as f64);
+    let samples: Vec<Vec<f64>> =
+        std::iter::from_fn(|| <Signals as Write<Vec<f64>, Sig>>::write(&mut signals))
+            .map(|data| (*data).clone())
+            .collect();
+    assert_eq!(
+        samples,
+        vec![
+            vec![0., 1.],
+            vec![1., 2.],
+            vec![4., 5.],
+            vec![9., 10.],
+            vec![16., 17.]
+        ]
+    );
+    ```
+    */
+    pub fn output_fn<F>(self, k: usize, f: F) -> Self
+    where
+        F: FnMut(usize) -> f64 + Send + 'static,
+    {
+        assert!(k < self.size, "output #{k} out of range");
+        let mut callbacks = self.callbacks;
+        callbacks.push((k, Callback(Arc::new(Mutex::new(Box::new(f))))));
+        Self { callbacks, ..self }
+    }
 }
 
 impl Add for Signal {
@@ -122,13 +603,38 @@ impl Add for Signal {
     }
 }
 
-impl Update for Signals {}
+impl Update for Signals {
+    #[cfg(feature = "noise")]
+    fn seed(&mut self, seed: u64) {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+    }
+}
 impl<U> Write<Vec<f64>, U> for Signals {
     fn write(&mut self) -> Option<Arc<Data<Vec<f64>, U>>> {
         log::debug!("write {:?}", self.size);
+        if self
+            .until
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+        {
+            return None;
+        }
         if self.step < self.n_step {
             let i = self.step;
-            let data = self.signals.iter().map(|signal| signal.get(i)).collect();
+            #[cfg(feature = "noise")]
+            let mut data: Vec<f64> = match self.rng.as_mut() {
+                Some(rng) => self
+                    .signals
+                    .iter()
+                    .map(|signal| signal.get_with(i, rng))
+                    .collect(),
+                None => self.signals.iter().map(|signal| signal.get(i)).collect(),
+            };
+            #[cfg(not(feature = "noise"))]
+            let mut data: Vec<f64> = self.signals.iter().map(|signal| signal.get(i)).collect();
+            for (k, callback) in &self.callbacks {
+                data[*k] += callback.get(i);
+            }
             self.step += 1;
             Some(Arc::new(Data::new(data)))
         } else {
@@ -0,0 +1,180 @@
+/*!
+# Wavefront metrics client
+
+Image quality proxies computed from the outputs of the [GMT LOM](https://docs.rs/gmt-lom).
+The client does not depend on the LOM itself and it is available without the `lom` feature.
+*/
+use crate::{
+    io::{Data, Read, Write},
+    Update,
+};
+use std::sync::Arc;
+
+/// Tip and tilt in the GMT focal plane
+pub enum TipTilt {}
+/// Segment tip and tilt in the GMT focal plane
+pub enum SegmentTipTilt {}
+/// Segment piston in the GMT exit pupil
+pub enum SegmentPiston {}
+
+/// GMT entrance pupil diameter `[m]`
+const GMT_DIAMETER: f64 = 25.5;
+/// GMT segment diameter `[m]`
+const SEGMENT_DIAMETER: f64 = 8.365;
+
+/// Wavefront error RMS `[m]`
+pub enum WavefrontError {}
+/// Strehl ratio
+pub enum Strehl {}
+/// Encircled energy
+pub enum EncircledEnergy {}
+
+/**
+Image quality proxies derived from the [LOM](https://docs.rs/gmt-lom) outputs
+
+The client reads the [TipTilt], the [SegmentTipTilt] and the [SegmentPiston] outputs of the LOM and writes:
+ - [WavefrontError]: the RMS of the segment piston and segment tip-tilt wavefront errors,
+ - [Strehl]: the Strehl ratio from the Maréchal approximation `exp(-(2π WFE/λ)²)`,
+ - [EncircledEnergy]: the fraction of the energy within a radius of the optical axis,
+   the image is a Gaussian core of standard deviation `0.42λ/D` weighted by the Strehl ratio and displaced by the tip-tilt.
+
+The tip-tilt are given in radians and the segment piston in meters.
+
+# Example
+The Strehl ratio drops as the segment piston grows
+```
+use dos_actors::{
+    clients::wavefront::{SegmentPiston, Strehl, WavefrontMetrics},
+    io::{Data, Read, Write},
+};
+use std::sync::Arc;
+let mut metrics = WavefrontMetrics::default();
+let mut strehl = |piston: f64| {
+    let data = vec![piston, -piston, piston, -piston, piston, -piston, 0.];
+    <WavefrontMetrics as Read<Vec<f64>, SegmentPiston>>::read(&mut metrics, Arc::new(Data::new(data)));
+    <WavefrontMetrics as Write<Vec<f64>, Strehl>>::write(&mut metrics).unwrap()[0]
+};
+let strehls: Vec<f64> = [1e-9, 1e-8, 5e-8].into_iter().map(strehl).collect();
+assert!(strehls[0] > 0.999);
+assert!(strehls[0] > strehls[1] && strehls[1] > strehls[2]);
+```
+
+Without aberrations, the energy within `λ/D` of the Gaussian core is `1-exp(-1/(2x0.42²))`
+and it drops as the image moves away from the optical axis
+```
+use dos_actors::{
+    clients::wavefront::{EncircledEnergy, TipTilt, WavefrontMetrics},
+    io::{Data, Read, Write},
+};
+use std::sync::Arc;
+let mut metrics = WavefrontMetrics::default();
+let mut ee = |tilt: f64| {
+    <WavefrontMetrics as Read<Vec<f64>, TipTilt>>::read(&mut metrics, Arc::new(Data::new(vec![tilt, 0.])));
+    <WavefrontMetrics as Write<Vec<f64>, EncircledEnergy>>::write(&mut metrics).unwrap()[0]
+};
+let ees: Vec<f64> = [0., 5e-9, 1e-8].into_iter().map(ee).collect();
+assert!((ees[0] - (1. - (-0.5 / 0.42f64.powi(2)).exp())).abs() < 1e-3);
+assert!(ees[0] > ees[1] && ees[1] > ees[2]);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct WavefrontMetrics {
+    wavelength: f64,
+    ee_radius: Option<f64>,
+    tiptilt: Vec<f64>,
+    segment_tiptilt: Vec<f64>,
+    segment_piston: Vec<f64>,
+}
+impl Default for WavefrontMetrics {
+    fn default() -> Self {
+        Self {
+            wavelength: 500e-9,
+            ee_radius: None,
+            tiptilt: vec![0f64; 2],
+            segment_tiptilt: vec![0f64; 14],
+            segment_piston: vec![0f64; 7],
+        }
+    }
+}
+impl WavefrontMetrics {
+    /// Sets the wavelength in meters (default: 500nm)
+    pub fn wavelength(self, wavelength: f64) -> Self {
+        Self { wavelength, ..self }
+    }
+    /// Sets the radius of the encircled energy in radians (default: λ/D)
+    pub fn ee_radius(self, ee_radius: f64) -> Self {
+        Self {
+            ee_radius: Some(ee_radius),
+            ..self
+        }
+    }
+    /// Returns the RMS of the wavefront error `[m]`
+    pub fn wfe_rms(&self) -> f64 {
+        let n = self.segment_piston.len().max(1) as f64;
+        let mean = self.segment_piston.iter().sum::<f64>() / n;
+        let piston_var = self
+            .segment_piston
+            .iter()
+            .map(|p| (p - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        // the RMS of a tilt θ over a disk of diameter D is θD/4
+        let tiptilt_var = self.segment_tiptilt.iter().map(|t| t * t).sum::<f64>() / n
+            * (0.25 * SEGMENT_DIAMETER).powi(2);
+        (piston_var + tiptilt_var).sqrt()
+    }
+    /// Returns the Strehl ratio
+    pub fn strehl(&self) -> f64 {
+        (-(2. * std::f64::consts::PI * self.wfe_rms() / self.wavelength).powi(2)).exp()
+    }
+    /// Returns the encircled energy
+    pub fn encircled_energy(&self) -> f64 {
+        let sigma = 0.42 * self.wavelength / GMT_DIAMETER;
+        let radius = self.ee_radius.unwrap_or(self.wavelength / GMT_DIAMETER);
+        let offset = self.tiptilt[0].hypot(self.tiptilt[1]);
+        // midpoint rule integration of the Gaussian core over the disk
+        let (n_r, n_o) = (32, 64);
+        let (dr, dtheta) = (radius / n_r as f64, 2. * std::f64::consts::PI / n_o as f64);
+        let energy: f64 = (0..n_r)
+            .flat_map(|i| (0..n_o).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let r = (i as f64 + 0.5) * dr;
+                let (sin, cos) = ((j as f64 + 0.5) * dtheta).sin_cos();
+                let d2 = (r * cos - offset).powi(2) + (r * sin).powi(2);
+                (-0.5 * d2 / (sigma * sigma)).exp() * r * dr * dtheta
+            })
+            .sum();
+        self.strehl() * energy / (2. * std::f64::consts::PI * sigma * sigma)
+    }
+}
+impl Update for WavefrontMetrics {}
+impl Read<Vec<f64>, TipTilt> for WavefrontMetrics {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, TipTilt>>) {
+        self.tiptilt = (**data).clone();
+    }
+}
+impl Read<Vec<f64>, SegmentTipTilt> for WavefrontMetrics {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, SegmentTipTilt>>) {
+        self.segment_tiptilt = (**data).clone();
+    }
+}
+impl Read<Vec<f64>, SegmentPiston> for WavefrontMetrics {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, SegmentPiston>>) {
+        self.segment_piston = (**data).clone();
+    }
+}
+impl Write<Vec<f64>, WavefrontError> for WavefrontMetrics {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, WavefrontError>>> {
+        Some(Arc::new(Data::new(vec![self.wfe_rms()])))
+    }
+}
+impl Write<Vec<f64>, Strehl> for WavefrontMetrics {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, Strehl>>> {
+        Some(Arc::new(Data::new(vec![self.strehl()])))
+    }
+}
+impl Write<Vec<f64>, EncircledEnergy> for WavefrontMetrics {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, EncircledEnergy>>> {
+        Some(Arc::new(Data::new(vec![self.encircled_energy()])))
+    }
+}
@@ -14,11 +14,15 @@ pub enum WindLoadsError {
     Load(#[from] parse_monitors::MonitorsError),
     #[error("coordinates transformation failed")]
     Coordinates(#[from] geotrans::Error),
+    #[error("{0} CFD loads nodes but {1} FEM inputs")]
+    FemSize(usize, usize),
+    #[error("CFD loads {0} do not match FEM input: {1}")]
+    FemMismatch(String, String),
 }
 pub type Result<T> = std::result::Result<T, WindLoadsError>;
 
 /// List of  all the CFD wind loads
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum WindLoads {
     TopEnd,
     M2Segments,
@@ -34,6 +38,29 @@ pub enum WindLoads {
     M1Segments,
 }
 impl WindLoads {
+    /// Returns all the CFD wind loads groups
+    pub fn all() -> Vec<WindLoads> {
+        use WindLoads::*;
+        vec![
+            TopEnd,
+            M2Segments,
+            M2Baffle,
+            Trusses,
+            M1Baffle,
+            MirrorCovers,
+            LaserGuideStars,
+            CRings,
+            GIR,
+            Platforms,
+            M1Segments,
+        ]
+    }
+    /// Returns the group the CFD monitor `key` belongs to
+    pub fn from_key(key: &str) -> Option<WindLoads> {
+        Self::all()
+            .into_iter()
+            .find(|group| group.keys().iter().any(|k| k == key))
+    }
     /// Returns the names of the CFD monitors
     pub fn keys(&self) -> Vec<String> {
         use WindLoads::*;
@@ -119,6 +146,7 @@ pub struct Builder<S> {
     duration: Option<f64>,
     time_range: Option<(f64, f64)>,
     nodes: Option<Vec<(String, CS)>>,
+    groups: Option<Vec<WindLoads>>,
     upsampling: S,
 }
 impl<S: Default> Builder<S> {
@@ -156,6 +184,15 @@ impl<S: Default> Builder<S> {
             ..self
         }
     }
+    /// Selects the groups of CFD loads that are applied
+    ///
+    /// The forces and moments of the nodes that do not belong to the selected groups are set to zero
+    pub fn select(self, groups: Vec<WindLoads>) -> Self {
+        Self {
+            groups: Some(groups),
+            ..self
+        }
+    }
     /// Requests M1 segments loads
     pub fn m1_segments(self) -> Self {
         let m1_nodes: Vec<_> = WindLoads::M1Segments
@@ -399,6 +436,31 @@ impl<S> Builder<S> {
             .as_ref()
             .map_or(m1_loads.as_ref().map_or(0, |x| x.len()), |x| x.len())
             / monitors.time.len();
+        let (mut data, mut m1_loads, mut m2_loads) = (data, m1_loads, m2_loads);
+        if let (Some(groups), Some(nodes)) = (self.groups.as_ref(), self.nodes.as_ref()) {
+            let is_selected =
+                |key: &str| WindLoads::from_key(key).map_or(false, |group| groups.contains(&group));
+            if let Some(data) = data.as_mut() {
+                let unselected: Vec<_> = nodes
+                    .iter()
+                    .filter(|(_, location)| matches!(location, CS::OSS(_)))
+                    .enumerate()
+                    .filter(|(_, (key, _))| !is_selected(key))
+                    .map(|(k, _)| k)
+                    .collect();
+                data.chunks_mut(n).for_each(|fm| {
+                    unselected
+                        .iter()
+                        .for_each(|&k| fm[k * 6..(k + 1) * 6].fill(0f64))
+                });
+            }
+            if !groups.contains(&WindLoads::M1Segments) {
+                m1_loads.iter_mut().flatten().for_each(|x| *x = 0f64);
+            }
+            if !groups.contains(&WindLoads::M2Segments) {
+                m2_loads.iter_mut().flatten().for_each(|x| *x = 0f64);
+            }
+        }
         Ok(CfdLoads {
             oss: data,
             m1: m1_loads,
@@ -516,6 +578,37 @@ impl<S> CfdLoads<S> {
                 .collect::<Vec<f64>>()
         })
     }
+    /// Validates the CFD loads nodes against the FEM inputs
+    ///
+    /// `descriptions` are the descriptions of the FEM inputs the OSS CFD loads are applied to,
+    /// one per node and in the same order than the nodes
+    pub fn validate_fem<D: AsRef<str>>(&self, descriptions: &[D]) -> Result<()> {
+        let keys: Vec<_> = self
+            .nodes
+            .iter()
+            .flatten()
+            .filter(|(_, location)| matches!(location, CS::OSS(_)))
+            .map(|(key, _)| key)
+            .collect();
+        if keys.len() != descriptions.len() {
+            return Err(WindLoadsError::FemSize(keys.len(), descriptions.len()));
+        }
+        for (key, description) in keys.into_iter().zip(descriptions) {
+            let description = description.as_ref();
+            if !WindLoads::from_key(key).map_or(false, |group| {
+                group
+                    .fem()
+                    .iter()
+                    .any(|pattern| description.contains(pattern.as_str()))
+            }) {
+                return Err(WindLoadsError::FemMismatch(
+                    key.to_string(),
+                    description.to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
     pub fn stop_after(&mut self, max_step: usize) -> &mut Self {
         self.max_step = max_step;
         self
@@ -709,3 +802,63 @@ impl Write<Vec<f64>, fem::fem_io::MCM2LclForce6F> for CfdLoads<FOH> {
         })
     }
 }
+
+/**
+Returns the force and moment resultant of the loads `fm` about the OSS origin
+
+`fm` is the concatenation of the forces and moments `[Fx,Fy,Fz,Mx,My,Mz]` applied at each node
+with the moments given about the node `locations` (`[x,y,z]`), the resultant is `[ΣF,Σ(M+r×F)]`.
+A node without location is located at the origin.
+
+# Example
+```
+use dos_actors::clients::windloads::load_resultant;
+let fm = vec![
+    1., 0., 0., 0., 0., 0., // Fx at (0,1,0)
+    0., 2., 0., 0., 0., 3., // Fy and Mz at (1,0,0)
+];
+let resultant = load_resultant(&fm, &[[0., 1., 0.], [1., 0., 0.]]);
+// Mz = 3 + (1,0,0)x(0,2,0) + (0,1,0)x(1,0,0) = 3 + 2 - 1
+assert_eq!(resultant, vec![1., 2., 0., 0., 0., 4.]);
+```
+*/
+pub fn load_resultant<L: AsRef<[f64]>>(fm: &[f64], locations: &[L]) -> Vec<f64> {
+    fm.chunks(6)
+        .enumerate()
+        .fold(vec![0f64; 6], |mut resultant, (k, fm)| {
+            let r = locations.get(k).map_or(&[0f64; 3][..], |r| r.as_ref());
+            let (f, m) = fm.split_at(3);
+            let r_x_f = [
+                r[1] * f[2] - r[2] * f[1],
+                r[2] * f[0] - r[0] * f[2],
+                r[0] * f[1] - r[1] * f[0],
+            ];
+            resultant[..3].iter_mut().zip(f).for_each(|(a, f)| *a += f);
+            resultant[3..]
+                .iter_mut()
+                .zip(m.iter().zip(&r_x_f))
+                .for_each(|(a, (m, r_x_f))| *a += m + r_x_f);
+            resultant
+        })
+}
+
+/// Net force and moment resultant of the OSS loads about the OSS origin (see [load_resultant])
+pub enum LoadResultant {}
+impl Write<Vec<f64>, LoadResultant> for CfdLoads<FOH> {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, LoadResultant>>> {
+        let locations: Vec<&[f64]> = self
+            .nodes
+            .iter()
+            .flatten()
+            .filter_map(|(_, location)| match location {
+                CS::OSS(location) => Some(location.as_slice()),
+                _ => None,
+            })
+            .collect();
+        self.oss.as_ref().and_then(|oss| {
+            self.upsampling
+                .sample(oss, self.n_fm)
+                .map(|fm| Arc::new(Data::new(load_resultant(&fm, &locations))))
+        })
+    }
+}
@@ -0,0 +1,328 @@
+/*!
+# Model configuration
+
+A [Model] made of some of the core [clients](crate::clients) is described with a [ModelConfig]
+that is saved to a JSON file with [ModelConfig::to_config] and loaded from the file with [Model::from_config].
+
+The module is enabled with the `serde` feature.
+
+All the actors of a configured model run at the simulation sampling rate and
+their inputs and outputs are `Vec<f64>` with the [ConfigData] UID.
+A configuration with an actor [rate](ActorConfig::rate) other than 1 is rejected with [ConfigError::Rate].
+Only the [Signals], [Integrator] and [Logging] clients can be configured,
+and a [ModelConfig] cannot be recovered from a [Model] as the actors clients are not serializable once in a model.
+
+# Example
+A signal integrated and logged
+```
+# tokio_test::block_on(async {
+use dos_actors::{config::ModelConfig, prelude::*};
+let path = std::env::temp_dir().join("model_config.json");
+ModelConfig::from_json(
+    r#"{
+    "name": "integration",
+    "actors": [
+        {
+            "name": "source",
+            "client": {
+                "Signals": {
+                    "size": 1,
+                    "signals": [{ "Constant": 1.0 }],
+                    "step": 0,
+                    "n_step": 5
+                }
+            }
+        },
+        { "name": "integrator", "client": { "Integrator": { "size": 1, "gain": 0.5 } } },
+        { "name": "logger", "client": "Logging" }
+    ],
+    "connections": [
+        { "producer": "source", "consumers": ["integrator"] },
+        { "producer": "integrator", "consumers": ["logger"] }
+    ]
+}"#,
+)?
+.to_config(&path)?;
+let (model, loggers) = Model::from_config(&path)?;
+model.check()?.run().wait().await?;
+assert_eq!(
+    **loggers["logger"].lock().await,
+    vec![-0.5, -1.0, -1.5, -2.0, -2.5]
+);
+let json = ModelConfig::from_json(&std::fs::read_to_string(&path)?)?.to_json()?;
+assert_eq!(ModelConfig::from_json(&json)?.to_json()?, json);
+# Ok::<(), anyhow::Error>(())
+# }).unwrap();
+```
+*/
+
+use crate::{
+    clients::{Integrator, Logging, Signals},
+    io,
+    model::{Model, Unknown},
+    Actor, AddOuput, ArcMutex, Initiator, IntoInputs, Task, Terminator, Update,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path, sync::Arc};
+use tokio::sync::Mutex;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("cannot read the model configuration file")]
+    Io(#[from] std::io::Error),
+    #[error("cannot parse the model configuration")]
+    Json(#[from] serde_json::Error),
+    #[error("actor {0} is not defined")]
+    UnknownActor(String),
+    #[error("actor {0} is defined more than once")]
+    DuplicateActor(String),
+    #[error("actor {0} cannot be the producer of a connection or is the producer of more than one connection")]
+    Producer(String),
+    #[error("actor {0} cannot be a consumer of a connection")]
+    Consumer(String),
+    #[error("the connection from {0} has no consumers")]
+    NoConsumers(String),
+    #[error("actor {0} rate ({1}) is not supported, the actors of a configured model run at the simulation sampling rate")]
+    Rate(String, usize),
+}
+type Result<T> = std::result::Result<T, ConfigError>;
+
+/// Data UID of the connections of a configured [Model]
+pub enum ConfigData {}
+
+/// The [Logging] clients of a configured [Model] indexed by actor name
+pub type Loggers = HashMap<String, Arc<Mutex<Logging<f64>>>>;
+
+/// Client configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClientConfig {
+    /// A [Signals] source, see [Signals::to_json]
+    Signals(Box<Signals>),
+    /// An [Integrator] of `size` elements with the same `gain` for all elements
+    Integrator { size: usize, gain: f64 },
+    /// A [Logging] sink with as many entries as connections
+    Logging,
+}
+
+/// Actor configuration
+///
+/// # Example
+/// A decimated actor is rejected
+/// ```
+/// use dos_actors::config::{ConfigError, ModelConfig};
+/// let config = ModelConfig::from_json(
+///     r#"{
+///     "actors": [
+///         { "name": "integrator", "client": { "Integrator": { "size": 1, "gain": 0.5 } }, "rate": 10 }
+///     ],
+///     "connections": []
+/// }"#,
+/// )?;
+/// assert!(matches!(config.build(), Err(ConfigError::Rate(name, 10)) if name == "integrator"));
+/// # Ok::<(), ConfigError>(())
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActorConfig {
+    /// Actor name, unique within the model
+    pub name: String,
+    /// Actor client
+    pub client: ClientConfig,
+    /// Actor sampling rate as a fraction of the simulation sampling rate, only 1 is supported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<usize>,
+}
+
+/// Connection configuration
+///
+/// A single output of the producer is multiplexed to all the consumers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionConfig {
+    /// Name of the actor the data is sent from
+    pub producer: String,
+    /// Names of the actors the data is sent to
+    pub consumers: Vec<String>,
+    /// Bootstrapped output
+    #[serde(default)]
+    pub bootstrap: bool,
+}
+
+/// Model configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelConfig {
+    /// Model name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Model actors
+    pub actors: Vec<ActorConfig>,
+    /// Connections between the actors, an actor is the producer of at most one connection
+    pub connections: Vec<ConnectionConfig>,
+}
+
+// Actor of a configured model
+enum ConfigActor {
+    Signals(Initiator<Signals>),
+    Integrator(Actor<Integrator<f64, ConfigData>>),
+    Logging(Terminator<Logging<f64>>),
+}
+impl ConfigActor {
+    fn into_task(self) -> Box<dyn Task> {
+        match self {
+            ConfigActor::Signals(actor) => Box::new(actor),
+            ConfigActor::Integrator(actor) => Box::new(actor),
+            ConfigActor::Logging(actor) => Box::new(actor),
+        }
+    }
+}
+
+// Adds an output to `actor` multiplexed to all the `consumers`
+fn wire<C, const NI: usize>(
+    actor: &mut Actor<C, NI, 1>,
+    consumers: Vec<&mut ConfigActor>,
+    bootstrap: bool,
+) where
+    C: 'static + Update + Send + io::Write<Vec<f64>, ConfigData>,
+{
+    let output = actor.add_output().multiplex(consumers.len());
+    let output = if bootstrap {
+        output.bootstrap()
+    } else {
+        output
+    };
+    consumers.into_iter().fold(
+        output.build::<Vec<f64>, ConfigData>(),
+        |output, consumer| match consumer {
+            ConfigActor::Integrator(actor) => output.into_input(actor),
+            ConfigActor::Logging(actor) => output.into_input(actor),
+            ConfigActor::Signals(_) => unreachable!("a source cannot be a consumer"),
+        },
+    );
+}
+
+impl ModelConfig {
+    /// Deserializes the model configuration from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+    /// Serializes the model configuration into JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+    /// Saves the model configuration into the JSON file at `path`, the model is built from the file with [Model::from_config]
+    pub fn to_config<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+    /// Builds the model and returns it with its [Loggers]
+    pub fn build(self) -> Result<(Model<Unknown>, Loggers)> {
+        let ModelConfig {
+            name,
+            actors,
+            connections,
+        } = self;
+        if let Some((name, rate)) = actors.iter().find_map(|actor| {
+            actor
+                .rate
+                .filter(|&rate| rate != 1)
+                .map(|rate| (actor.name.clone(), rate))
+        }) {
+            return Err(ConfigError::Rate(name, rate));
+        }
+        let names: Vec<String> = actors.iter().map(|actor| actor.name.clone()).collect();
+        if let Some(name) = names
+            .iter()
+            .enumerate()
+            .find_map(|(k, name)| names[..k].contains(name).then_some(name))
+        {
+            return Err(ConfigError::DuplicateActor(name.clone()));
+        }
+        let index = |name: &String| {
+            names
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| ConfigError::UnknownActor(name.clone()))
+        };
+
+        let mut loggers = Loggers::new();
+        let mut actors: Vec<Option<ConfigActor>> = actors
+            .into_iter()
+            .map(|ActorConfig { name, client, .. }| {
+                Some(match client {
+                    ClientConfig::Signals(signals) => ConfigActor::Signals((*signals, name).into()),
+                    ClientConfig::Integrator { size, gain } => {
+                        ConfigActor::Integrator((Integrator::new(size).gain(gain), name).into())
+                    }
+                    ClientConfig::Logging => {
+                        let n_entry = connections
+                            .iter()
+                            .filter(|connection| connection.consumers.contains(&name))
+                            .count();
+                        let logging = Logging::<f64>::default()
+                            .n_entry(n_entry.max(1))
+                            .into_arcx();
+                        loggers.insert(name.clone(), logging.clone());
+                        ConfigActor::Logging(Terminator::new(logging).named(name))
+                    }
+                })
+            })
+            .collect();
+
+        let mut producers = vec![];
+        for connection in &connections {
+            let k = index(&connection.producer)?;
+            if producers.contains(&k) {
+                return Err(ConfigError::Producer(connection.producer.clone()));
+            }
+            producers.push(k);
+            let consumers = connection
+                .consumers
+                .iter()
+                .map(index)
+                .collect::<Result<Vec<usize>>>()?;
+            if consumers.is_empty() {
+                return Err(ConfigError::NoConsumers(connection.producer.clone()));
+            }
+            if let Some(&i) = consumers
+                .iter()
+                .find(|&&i| i == k || matches!(actors[i], Some(ConfigActor::Signals(_))))
+            {
+                return Err(ConfigError::Consumer(names[i].clone()));
+            }
+            let mut producer = actors[k].take().expect("the producer has been taken");
+            let consumers: Vec<&mut ConfigActor> = actors
+                .iter_mut()
+                .enumerate()
+                .filter(|(i, _)| consumers.contains(i))
+                .filter_map(|(_, actor)| actor.as_mut())
+                .collect();
+            match &mut producer {
+                ConfigActor::Signals(actor) => wire(actor, consumers, connection.bootstrap),
+                ConfigActor::Integrator(actor) => wire(actor, consumers, connection.bootstrap),
+                ConfigActor::Logging(_) => {
+                    return Err(ConfigError::Producer(connection.producer.clone()))
+                }
+            }
+            actors[k] = Some(producer);
+        }
+
+        let model = Model::new(
+            actors
+                .into_iter()
+                .flatten()
+                .map(ConfigActor::into_task)
+                .collect(),
+        );
+        let model = match name {
+            Some(name) => model.name(name),
+            None => model,
+        };
+        Ok((model, loggers))
+    }
+}
+
+impl Model<Unknown> {
+    /// Builds a model from the [ModelConfig] in the JSON file at `path`, returns the model and its [Loggers]
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<(Self, Loggers)> {
+        ModelConfig::from_json(&fs::read_to_string(path)?)?.build()
+    }
+}
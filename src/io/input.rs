@@ -1,10 +1,12 @@
-use super::{Read, S};
-use crate::{Result, Who};
+use super::{Read, ReadOption, Receiver, S};
+use crate::{ActorError, Result, Who};
 use async_trait::async_trait;
-use flume::Receiver;
-use std::sync::Arc;
+use std::{any::Any, sync::Arc, time::Duration};
 use tokio::sync::Mutex;
 
+// Length of the data received by an input
+type Len<T> = fn(&T) -> usize;
+
 /// [Actor](crate::Actor)s input
 pub(crate) struct Input<C, T, U, const N: usize>
 where
@@ -12,14 +14,44 @@ where
 {
     rx: Receiver<S<T, U>>,
     client: Arc<Mutex<C>>,
+    coalescing: bool,
+    id: usize,
+    size: Option<(usize, Len<T>)>,
+    ordered: bool,
+    last_step: Option<usize>,
+    data: Option<S<T, U>>,
 }
 impl<C, T, U, const N: usize> Input<C, T, U, N>
 where
     C: Read<T, U>,
 {
-    /// Creates a new intput from a [Receiver] and an [Actor] client
-    pub fn new(rx: Receiver<S<T, U>>, client: Arc<Mutex<C>>) -> Self {
-        Self { rx, client }
+    /// Creates a new intput from a [Receiver], the receiver channel identifier and an [Actor] client
+    pub fn new(rx: Receiver<S<T, U>>, id: usize, client: Arc<Mutex<C>>) -> Self {
+        Self {
+            rx,
+            client,
+            coalescing: false,
+            id,
+            size: None,
+            ordered: false,
+            last_step: None,
+            data: None,
+        }
+    }
+    /// Only reads the most recent of the queued data
+    pub fn coalescing(self, coalescing: bool) -> Self {
+        Self { coalescing, ..self }
+    }
+    /// Validates that the size of the data, given by `len`, is `n`
+    pub fn size(self, n: usize, len: Len<T>) -> Self {
+        Self {
+            size: Some((n, len)),
+            ..self
+        }
+    }
+    /// Validates that the steps the data are stamped with are strictly increasing
+    pub fn ordered(self, ordered: bool) -> Self {
+        Self { ordered, ..self }
     }
 }
 impl<C: Read<T, U>, T, U, const N: usize> Who<U> for Input<C, T, U, N> {}
@@ -28,26 +60,234 @@ impl<C: Read<T, U>, T, U, const N: usize> Who<U> for Input<C, T, U, N> {}
 pub(crate) trait InputObject: Send + Sync {
     /// Receives output data
     async fn recv(&mut self) -> Result<()>;
+    /// Passes the received data to the client, the data is dropped if the actor is bypassed
+    async fn read(&mut self, bypass: bool);
     fn who(&self) -> String;
+    /// Returns the unique identifier of the input channel
+    fn channel_id(&self) -> usize;
+    /// Returns `true` if the input does not wait for the data longer than a timeout
+    fn is_optional(&self) -> bool;
+    /**
+    Replaces the receiver if `id` is the identifier of the input channel
+
+    Returns `rx` if it does not belong to the input
+    */
+    fn rewire(&mut self, id: usize, rx: Box<dyn Any + Send>) -> Option<Box<dyn Any + Send>>;
+    /**
+    Replaces the receiver, and the input channel identifier with `id`, if all the senders of the input channel have been dropped
+
+    Returns `rx` if the input is still connected or if `rx` is not of the same type
+    */
+    fn rewire_dangling(
+        &mut self,
+        id: usize,
+        rx: Box<dyn Any + Send>,
+    ) -> Option<Box<dyn Any + Send>>;
+}
+
+// Updates the `last_step` if `step` is greater, otherwise returns the `last_step`
+fn check_order(step: usize, last_step: &mut Option<usize>) -> std::result::Result<(), usize> {
+    match *last_step {
+        Some(previous) if step <= previous => Err(previous),
+        _ => {
+            *last_step = Some(step);
+            Ok(())
+        }
+    }
+}
+
+// Replaces `rx` with `new_rx` if `new_rx` is a receiver of the same type
+fn rewire<T: 'static, U: 'static>(
+    rx: &mut Receiver<S<T, U>>,
+    new_rx: Box<dyn Any + Send>,
+) -> Option<Box<dyn Any + Send>> {
+    match new_rx.downcast::<Receiver<S<T, U>>>() {
+        Ok(new_rx) => {
+            *rx = *new_rx;
+            None
+        }
+        Err(new_rx) => Some(new_rx),
+    }
+}
+
+/// [Actor](crate::Actor)s optional input
+///
+/// The client reads [None] if no data is received within the timeout
+pub(crate) struct OptionalInput<C, T, U, const N: usize>
+where
+    C: ReadOption<T, U>,
+{
+    rx: Receiver<S<T, U>>,
+    client: Arc<Mutex<C>>,
+    timeout: Duration,
+    id: usize,
+    ordered: bool,
+    last_step: Option<usize>,
+    data: Option<S<T, U>>,
+}
+impl<C, T, U, const N: usize> OptionalInput<C, T, U, N>
+where
+    C: ReadOption<T, U>,
+{
+    /// Creates a new optional intput from a [Receiver], the receiver channel identifier, an [Actor] client and a timeout
+    pub fn new(rx: Receiver<S<T, U>>, id: usize, client: Arc<Mutex<C>>, timeout: Duration) -> Self {
+        Self {
+            rx,
+            client,
+            timeout,
+            id,
+            ordered: false,
+            last_step: None,
+            data: None,
+        }
+    }
+    /// Validates that the steps the received data are stamped with are strictly increasing
+    pub fn ordered(self, ordered: bool) -> Self {
+        Self { ordered, ..self }
+    }
+}
+impl<C: ReadOption<T, U>, T, U, const N: usize> Who<U> for OptionalInput<C, T, U, N> {}
+
+#[async_trait]
+impl<C, T, U, const N: usize> InputObject for OptionalInput<C, T, U, N>
+where
+    C: ReadOption<T, U> + Send,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync,
+{
+    async fn recv(&mut self) -> Result<()> {
+        log::debug!("{} receiving", Who::who(self));
+        self.data = match tokio::time::timeout(self.timeout, self.rx.recv()).await {
+            Ok(data) => Some(data?),
+            Err(_) => {
+                log::debug!("{} timed out", Who::who(self));
+                None
+            }
+        };
+        if let (true, Some(step)) = (
+            self.ordered,
+            self.data.as_ref().and_then(|data| data.step()),
+        ) {
+            check_order(step, &mut self.last_step).map_err(|previous| ActorError::OutOfOrder {
+                step,
+                previous,
+                data: Who::who(self),
+            })?;
+        }
+        log::debug!("{} received", Who::who(self));
+        Ok(())
+    }
+    async fn read(&mut self, bypass: bool) {
+        let data = self.data.take();
+        if !bypass {
+            (*self.client.lock().await).read_option(data)
+        }
+    }
+    fn who(&self) -> String {
+        Who::who(self)
+    }
+    fn channel_id(&self) -> usize {
+        self.id
+    }
+    fn is_optional(&self) -> bool {
+        true
+    }
+    fn rewire(&mut self, id: usize, rx: Box<dyn Any + Send>) -> Option<Box<dyn Any + Send>> {
+        if id == self.id {
+            rewire(&mut self.rx, rx)
+        } else {
+            Some(rx)
+        }
+    }
+    fn rewire_dangling(
+        &mut self,
+        id: usize,
+        rx: Box<dyn Any + Send>,
+    ) -> Option<Box<dyn Any + Send>> {
+        if !self.rx.is_disconnected() {
+            return Some(rx);
+        }
+        let rx = rewire(&mut self.rx, rx);
+        if rx.is_none() {
+            self.id = id;
+        }
+        rx
+    }
 }
 
 #[async_trait]
 impl<C, T, U, const N: usize> InputObject for Input<C, T, U, N>
 where
     C: Read<T, U> + Send,
-    T: Send + Sync,
-    U: Send + Sync,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync,
 {
     async fn recv(&mut self) -> Result<()> {
         log::debug!("{} receiving", Who::who(self));
-        log::debug!("{} receiving (locking client)", Who::who(self));
-        let mut client = self.client.lock().await;
-        log::debug!("{} receiving (client locked)", Who::who(self));
-        (*client).read(self.rx.recv_async().await?);
+        let mut data = self.rx.recv().await?;
+        if self.coalescing {
+            while let Some(newer) = self.rx.try_recv() {
+                data = newer;
+            }
+        }
+        if let Some((expected, len)) = self.size {
+            let got = len(&**data);
+            if got != expected {
+                return Err(ActorError::LengthMismatch {
+                    expected,
+                    got,
+                    data: Who::who(self),
+                });
+            }
+        }
+        if let (true, Some(step)) = (self.ordered, data.step()) {
+            check_order(step, &mut self.last_step).map_err(|previous| ActorError::OutOfOrder {
+                step,
+                previous,
+                data: Who::who(self),
+            })?;
+        }
+        self.data = Some(data);
         log::debug!("{} received", Who::who(self));
         Ok(())
     }
+    async fn read(&mut self, bypass: bool) {
+        if let (false, Some(data)) = (bypass, self.data.take()) {
+            // the client is locked once the data is received, so a client shared with other actors is not blocked while waiting
+            log::debug!("{} reading (locking client)", Who::who(self));
+            let mut client = self.client.lock().await;
+            log::debug!("{} reading (client locked)", Who::who(self));
+            (*client).read(data);
+        }
+    }
     fn who(&self) -> String {
         Who::who(self)
     }
+    fn channel_id(&self) -> usize {
+        self.id
+    }
+    fn is_optional(&self) -> bool {
+        false
+    }
+    fn rewire(&mut self, id: usize, rx: Box<dyn Any + Send>) -> Option<Box<dyn Any + Send>> {
+        if id == self.id {
+            rewire(&mut self.rx, rx)
+        } else {
+            Some(rx)
+        }
+    }
+    fn rewire_dangling(
+        &mut self,
+        id: usize,
+        rx: Box<dyn Any + Send>,
+    ) -> Option<Box<dyn Any + Send>> {
+        if !self.rx.is_disconnected() {
+            return Some(rx);
+        }
+        let rx = rewire(&mut self.rx, rx);
+        if rx.is_none() {
+            self.id = id;
+        }
+        rx
+    }
 }
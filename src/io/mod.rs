@@ -3,6 +3,7 @@
 
 [Actor]s communicate using channels, one input of an [actor] send data through
 either a [bounded] or an [unbounded] channel to an output of another actor.
+The channels are created by a [Transport], [flume] channels by default.
 The data that moves through a channel is encapsulated into a [Data] structure.
 
 Each input and output has a reference to the [Actor] client that reads data from
@@ -15,6 +16,8 @@ and [Write] traits.
 */
 
 use crate::Who;
+#[cfg(feature = "nalgebra")]
+use nalgebra::DVector;
 use std::{
     fmt,
     marker::PhantomData,
@@ -23,14 +26,20 @@ use std::{
 };
 
 mod input;
-pub(crate) use input::{Input, InputObject};
+pub(crate) use input::{Input, InputObject, OptionalInput};
 mod output;
 pub(crate) use output::{Output, OutputObject};
+mod transport;
+pub(crate) use transport::{channel, transport_channel};
+pub use transport::{
+    Flume, Receiver, RecvError, SendError, Sender, Transport, TransportReceiver, TransportSender,
+};
 
 /// input/output data
 ///
-/// `T` is the data primitive type and `U` is the data unique identifier (UID)
-pub struct Data<T, U>(T, PhantomData<U>);
+/// `T` is the data primitive type and `U` is the data unique identifier (UID),
+/// the data may optionally carry the simulation step it was produced at (see [Data::stamped])
+pub struct Data<T, U>(T, PhantomData<U>, Option<usize>);
 impl<T, U> Deref for Data<T, U> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
@@ -45,7 +54,50 @@ impl<T, U> DerefMut for Data<T, U> {
 impl<T, U> Data<T, U> {
     /// Create a new [Data] object
     pub fn new(data: T) -> Self {
-        Data(data, PhantomData)
+        Data(data, PhantomData, None)
+    }
+    /**
+    Stamps the data with the simulation `step`
+
+    # Example
+    The producer stamps the data with its step and the stamps are stored by the [Logging](crate::clients::Logging) consumer
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{io, prelude::*, Update};
+    use std::sync::Arc;
+    struct Counter(usize);
+    impl Update for Counter {}
+    impl<U> io::Write<Vec<f64>, U> for Counter {
+        fn write(&mut self) -> Option<Arc<io::Data<Vec<f64>, U>>> {
+            self.0 += 1;
+            if self.0 > 5 {
+                None
+            } else {
+                Some(Arc::new(io::Data::new(vec![self.0 as f64]).stamped(self.0 * 10)))
+            }
+        }
+    }
+    enum Sig {};
+    let mut source: Initiator<_> = Counter(0).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert_eq!(logging.lock().await.stamps(), &[10, 20, 30, 40, 50]);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn stamped(self, step: usize) -> Self {
+        Data(self.0, PhantomData, Some(step))
+    }
+    /// Returns the simulation step the data is stamped with, if any
+    pub fn step(&self) -> Option<usize> {
+        self.2
     }
 }
 impl<T, U> From<&Data<Vec<T>, U>> for Vec<T>
@@ -59,13 +111,42 @@ where
 impl<T, U> From<Vec<T>> for Data<Vec<T>, U> {
     /// Returns data UID
     fn from(u: Vec<T>) -> Self {
-        Data(u, PhantomData)
+        Data::new(u)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T, U> From<DVector<T>> for Data<DVector<T>, U> {
+    /// Returns data UID
+    fn from(u: DVector<T>) -> Self {
+        Data::new(u)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T, U> From<&Data<Vec<T>, U>> for DVector<T>
+where
+    T: nalgebra::Scalar,
+{
+    fn from(data: &Data<Vec<T>, U>) -> Self {
+        DVector::from_column_slice(data)
+    }
+}
+#[cfg(feature = "nalgebra")]
+impl<T, U> From<&Data<DVector<T>, U>> for Vec<T>
+where
+    T: nalgebra::Scalar,
+{
+    fn from(data: &Data<DVector<T>, U>) -> Self {
+        data.as_slice().to_vec()
     }
 }
 impl<T, U> Who<U> for Data<T, U> {}
 impl<T: fmt::Debug, U> fmt::Debug for Data<T, U> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct(&self.who()).field("data", &self.0).finish()
+        let mut s = f.debug_struct(&self.who());
+        if let Some(step) = self.2 {
+            s.field("step", &step);
+        }
+        s.field("data", &self.0).finish()
     }
 }
 impl<T: Default, U> Default for Data<Vec<T>, U> {
@@ -74,14 +155,214 @@ impl<T: Default, U> Default for Data<Vec<T>, U> {
     }
 }
 
+/**
+2D frame
+
+The frame pixels are stored in row-major order with the frame `width` and `height`.
+
+# Example
+A frame going through a [Sampler](crate::clients::Sampler)
+```
+# tokio_test::block_on(async {
+use dos_actors::{
+    io::{Data, Frame, Write},
+    prelude::*,
+    Update,
+};
+use std::sync::Arc;
+struct Camera(usize);
+impl Update for Camera {}
+enum Image {};
+impl Write<Frame<f32>, Image> for Camera {
+    fn write(&mut self) -> Option<Arc<Data<Frame<f32>, Image>>> {
+        if self.0 == 0 {
+            return None;
+        }
+        self.0 -= 1;
+        Some(Arc::new(Data::new(Frame::new(vec![self.0 as f32; 12], 4, 3))))
+    }
+}
+let mut camera: Initiator<_> = Camera(5).into();
+let mut sampler: Actor<_> = Sampler::<Frame<f32>, Image>::default().into();
+let logging = Logging::<Frame<f32>>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+camera.add_output().build::<Frame<f32>, Image>().into_input(&mut sampler);
+sampler.add_output().build::<Frame<f32>, Image>().into_input(&mut sink);
+Model::new(vec![Box::new(camera), Box::new(sampler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let frames = logging.lock().await;
+assert_eq!(frames.len(), 5);
+assert!(frames.iter().all(|frame| frame.shape() == (4, 3)));
+assert_eq!(frames[4].as_slice(), &[0f32; 12]);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+impl<T> Frame<T> {
+    /// Creates a new frame from the pixels in row-major order
+    pub fn new(data: Vec<T>, width: usize, height: usize) -> Self {
+        assert_eq!(
+            data.len(),
+            width * height,
+            "frame size ({}) do not match the frame shape ({}x{})",
+            data.len(),
+            width,
+            height
+        );
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+    /// Returns the frame width
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// Returns the frame height
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Returns the frame shape `(width, height)`
+    pub fn shape(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+    /// Returns the pixel in column `x` and row `y`
+    pub fn pixel(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.data[y * self.width + x])
+    }
+    /// Returns the pixels
+    pub fn into_inner(self) -> Vec<T> {
+        self.data
+    }
+}
+impl<T> Deref for Frame<T> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+impl<T, U> From<Frame<T>> for Data<Frame<T>, U> {
+    fn from(frame: Frame<T>) -> Self {
+        Data::new(frame)
+    }
+}
+
 pub(crate) type S<T, U> = Arc<Data<T, U>>;
 
+/// Returns the slice `range` of the data, or the length of the data if it ends before the range
+pub type Slice<T> = fn(&T, std::ops::Range<usize>) -> std::result::Result<T, usize>;
+
 /// Client input data reader interface
 pub trait Read<T, U> {
     /// Read data from an input
     fn read(&mut self, data: Arc<Data<T, U>>);
 }
-/// Client output data writer interface
+/// Client optional input data reader interface
+///
+/// `data` is [None] if no data has been received within the input timeout
+pub trait ReadOption<T, U> {
+    /// Read data from an optional input
+    fn read_option(&mut self, data: Option<Arc<Data<T, U>>>);
+}
+/**
+Client output data writer interface
+
+Writing [None] ends the actor loop and disconnects the receivers of the output right away,
+even if the actor is still waiting on another of its outputs.
+
+# Example
+The source below stops writing `A` while it is blocked on sending `B` to a sink
+that waits for the consumer of `A` to stop
+```
+use dos_actors::{
+    clients::Sink,
+    io::{Data, Read, Write},
+    prelude::*,
+    Update,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+enum A {};
+enum B {};
+struct Source(usize);
+impl Update for Source {
+    fn update(&mut self) {
+        self.0 += 1;
+    }
+}
+impl Write<Vec<f64>, A> for Source {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, A>>> {
+        (self.0 < 5).then(|| Arc::new(Data::new(vec![self.0 as f64])))
+    }
+}
+impl Write<Vec<f64>, B> for Source {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, B>>> {
+        Some(Arc::new(Data::new(vec![self.0 as f64])))
+    }
+}
+// Raises a flag when it is dropped with its actor
+struct Watch(Arc<AtomicBool>);
+impl Drop for Watch {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+impl Update for Watch {}
+impl Read<Vec<f64>, A> for Watch {
+    fn read(&mut self, _data: Arc<Data<Vec<f64>, A>>) {}
+}
+tokio::runtime::Builder::new_multi_thread()
+    .worker_threads(4)
+    .enable_time()
+    .build()
+    .unwrap()
+    .block_on(async {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let flag = stopped.clone();
+        let in_time = Arc::new(AtomicBool::new(false));
+        let seen = in_time.clone();
+        let mut source: Initiator<_> = Source(0).into();
+        let mut a_sink = Terminator::<_>::new(Watch(stopped.clone()).into_arcx());
+        // after the sample #3, waits for the consumer of `A` to stop (or gives up after 5s)
+        let mut b_sink: Terminator<_> = Sink::<B>::new(move |_, data: &Vec<f64>| {
+            if data[0] == 3. {
+                let now = Instant::now();
+                tokio::task::block_in_place(|| {
+                    while !flag.load(Ordering::SeqCst) && now.elapsed().as_secs() < 5 {
+                        std::thread::sleep(Duration::from_millis(1));
+                    }
+                });
+                seen.store(flag.load(Ordering::SeqCst), Ordering::SeqCst);
+            }
+        })
+        .into();
+        source.add_output().build::<Vec<f64>, A>().into_input(&mut a_sink);
+        source.add_output().build::<Vec<f64>, B>().into_input(&mut b_sink);
+        let model = Model::new(vec![Box::new(source), Box::new(a_sink), Box::new(b_sink)])
+            .check()?
+            .run();
+        model.wait().await?;
+        assert!(in_time.load(Ordering::SeqCst));
+        Ok::<(), dos_actors::model::ModelError>(())
+    })?;
+# Ok::<(), dos_actors::model::ModelError>(())
+```
+*/
 pub trait Write<T, U> {
     fn write(&mut self) -> Option<Arc<Data<T, U>>>;
 }
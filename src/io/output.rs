@@ -1,42 +1,108 @@
-use super::{Write, S};
+use super::{Data, Receiver, Sender, Slice, Write, S};
 use crate::{ActorError, Result, Who};
 use async_trait::async_trait;
-use flume::Sender;
 use futures::future::join_all;
-use std::sync::Arc;
+use std::{any::Any, ops::Range, sync::Arc};
 use tokio::sync::Mutex;
 
+// Channel factory of an output
+type Channel<T, U> = fn(usize) -> (Sender<S<T, U>>, Receiver<S<T, U>>);
+
 pub(crate) struct OutputBuilder<C, T, U, const N: usize>
 where
     C: Write<T, U>,
 {
     tx: Vec<Sender<S<T, U>>>,
+    ids: Vec<usize>,
     client: Arc<Mutex<C>>,
     bootstrap: bool,
+    seed: Option<S<T, U>>,
+    throttle: Option<usize>,
+    backpressure: usize,
+    ranges: Option<(Vec<Range<usize>>, Slice<T>)>,
+    channel: Channel<T, U>,
+    label: Option<String>,
+    ordered: bool,
 }
 impl<C, T, U, const N: usize> OutputBuilder<C, T, U, N>
 where
     C: Write<T, U>,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync,
 {
     pub fn new(client: Arc<Mutex<C>>) -> Self {
         Self {
             tx: Vec::new(),
+            ids: Vec::new(),
             client,
             bootstrap: false,
+            seed: None,
+            throttle: None,
+            backpressure: usize::MAX,
+            ranges: None,
+            channel: super::channel::<super::Flume, S<T, U>>,
+            label: None,
+            ordered: false,
         }
     }
     pub fn senders(self, tx: Vec<Sender<S<T, U>>>) -> Self {
         Self { tx, ..self }
     }
+    /// Sets the unique identifiers of the channels of each sender
+    pub fn ids(self, ids: Vec<usize>) -> Self {
+        Self { ids, ..self }
+    }
     pub fn bootstrap(self, bootstrap: bool) -> Self {
         Self { bootstrap, ..self }
     }
+    /// Sets the data sent when bootstrapping in place of the client data
+    pub fn seed(self, seed: Option<S<T, U>>) -> Self {
+        Self { seed, ..self }
+    }
+    /// Sends only 1 every `k` samples
+    pub fn throttle(self, throttle: Option<usize>) -> Self {
+        Self { throttle, ..self }
+    }
+    /// Warns once if the channels are full for `k` consecutive sends
+    pub fn backpressure(self, backpressure: usize) -> Self {
+        Self {
+            backpressure,
+            ..self
+        }
+    }
+    /// Sends the slice `ranges[k]` of the data to the sender #`k`
+    pub fn demultiplex(self, ranges: Option<(Vec<Range<usize>>, Slice<T>)>) -> Self {
+        Self { ranges, ..self }
+    }
+    /// Sets the factory of the channels created when the output is resized
+    pub fn channel(self, channel: Channel<T, U>) -> Self {
+        Self { channel, ..self }
+    }
+    /// Sets the output label
+    pub fn label(self, label: Option<String>) -> Self {
+        Self { label, ..self }
+    }
+    /// Flags the inputs connected to the output to validate the order of the data
+    pub fn ordered(self, ordered: bool) -> Self {
+        Self { ordered, ..self }
+    }
     pub fn build(self) -> Output<C, T, U, N> {
         Output {
             data: None,
             tx: self.tx,
+            ids: self.ids,
             client: self.client,
             bootstrap: self.bootstrap,
+            seed: self.seed,
+            throttle: self.throttle,
+            n_write: 0,
+            backpressure: self.backpressure,
+            n_blocked: 0,
+            ranges: self.ranges,
+            nan_guard: false,
+            channel: self.channel,
+            label: self.label,
+            ordered: self.ordered,
         }
     }
 }
@@ -48,62 +114,188 @@ where
 {
     data: Option<S<T, U>>,
     tx: Vec<Sender<S<T, U>>>,
+    ids: Vec<usize>,
     client: Arc<Mutex<C>>,
     bootstrap: bool,
+    seed: Option<S<T, U>>,
+    throttle: Option<usize>,
+    n_write: usize,
+    backpressure: usize,
+    n_blocked: usize,
+    ranges: Option<(Vec<Range<usize>>, Slice<T>)>,
+    nan_guard: bool,
+    channel: Channel<T, U>,
+    label: Option<String>,
+    ordered: bool,
 }
 impl<C, T, U, const N: usize> Output<C, T, U, N>
 where
     C: Write<T, U>,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync,
 {
     /// Creates a new output from a [Sender] and data [Default]
     pub fn builder(client: Arc<Mutex<C>>) -> OutputBuilder<C, T, U, N> {
         OutputBuilder::new(client)
     }
+    // Returns the output data type name followed by the output label, if any
+    fn name(&self) -> String {
+        match &self.label {
+            Some(label) => format!("{} ({})", Who::who(self), label),
+            None => Who::who(self),
+        }
+    }
+    /// Sends `data` to all the receivers, or a slice of `data` to each receiver if the output is demultiplexed
+    async fn send_data(&self, data: &S<T, U>) -> Result<()> {
+        log::debug!("{} sending", self.name());
+        let futures: Vec<_> = match &self.ranges {
+            Some((ranges, slice)) => self
+                .tx
+                .iter()
+                .zip(ranges)
+                .map(|(tx, range)| {
+                    let sliced =
+                        slice(data, range.clone()).map_err(|got| ActorError::LengthMismatch {
+                            expected: range.end,
+                            got,
+                            data: self.name(),
+                        })?;
+                    Ok(tx.send(Arc::new(match data.step() {
+                        Some(step) => Data::new(sliced).stamped(step),
+                        None => Data::new(sliced),
+                    })))
+                })
+                .collect::<Result<_>>()?,
+            None => self.tx.iter().map(|tx| tx.send(data.clone())).collect(),
+        };
+        join_all(futures)
+            .await
+            .into_iter()
+            .collect::<std::result::Result<Vec<()>, super::SendError>>()?;
+        log::debug!("{} sent", self.name());
+        Ok(())
+    }
+    // Checks if any of the channels is full
+    fn is_full(&self) -> bool {
+        self.tx.iter().any(|tx| tx.is_full())
+    }
+    /// Sends the last data written by the client, the receivers are disconnected if there is none
+    ///
+    /// `full` tells if any of the channels was full when the client was written to
+    async fn dispatch(&mut self, full: bool) -> Result<()> {
+        if let Some(data) = &self.data {
+            if self.nan_guard && !is_finite(&***data) {
+                // the non-finite data are not sent and the receivers are disconnected
+                self.tx.clear();
+                return Err(ActorError::NonFinite(self.name()));
+            }
+            if let Some(k) = self.throttle {
+                let skip = !self.n_write.is_multiple_of(k);
+                self.n_write += 1;
+                if skip {
+                    return Ok(());
+                }
+            }
+            // the count stops once the warning has been issued
+            if self.n_blocked < self.backpressure {
+                if full {
+                    self.n_blocked += 1;
+                    if self.n_blocked == self.backpressure {
+                        log::warn!(
+                            "{} -> {}: blocked on a full channel for {} consecutive steps, the consumer may be too slow",
+                            std::any::type_name::<C>(),
+                            self.name(),
+                            self.n_blocked
+                        );
+                    }
+                } else {
+                    self.n_blocked = 0;
+                }
+            }
+            self.send_data(data).await
+        } else {
+            // dropping the senders disconnects the receivers once they have drained the channels
+            self.tx.clear();
+            Err(ActorError::Disconnected(self.name()))
+        }
+    }
 }
 impl<C, T, U, const N: usize> Who<U> for Output<C, T, U, N> where C: Write<T, U> {}
 
+// Checks that all the values of `data` are finite, only `Vec<f64>` data is checked
+fn is_finite<T: 'static>(data: &T) -> bool {
+    match (data as &dyn Any).downcast_ref::<Vec<f64>>() {
+        Some(values) => values.iter().all(|value| value.is_finite()),
+        None => true,
+    }
+}
+
 #[async_trait]
 pub(crate) trait OutputObject: Send + Sync {
     async fn send(&mut self) -> Result<()>;
+    /// Sends the last data again, or the client data if no data has been sent yet
+    async fn hold(&mut self) -> Result<()>;
+    /// Sends the bootstrap data if any, otherwise sends the client data
+    async fn send_bootstrap(&mut self) -> Result<()>;
     fn bootstrap(&self) -> bool;
+    fn throttle(&self) -> Option<usize>;
     fn len(&self) -> usize;
     fn who(&self) -> String;
+    /// Returns the unique identifier of the channel of sender #`k`
+    fn channel_id(&self, k: usize) -> usize;
+    /// Returns the channels capacity, [usize::MAX] if unbounded
+    fn capacity(&self) -> usize;
+    /**
+    Replaces the bounded channels with channels of at least `capacity`
+
+    Returns the receivers of the new channels with the identifiers of the channels they replace
+    */
+    fn resize(&mut self, capacity: usize) -> Vec<(usize, Box<dyn Any + Send>)>;
+    /// Removes the senders which receivers have been dropped
+    fn prune(&mut self);
+    /// Checks the data for non-finite values before sending them
+    fn nan_guard(&mut self);
+    /// Returns the output label
+    fn label(&self) -> Option<String>;
+    /// Returns `true` if the inputs connected to the output validate the order of the data
+    fn ordered(&self) -> bool;
 }
 #[async_trait]
 impl<C, T, U, const N: usize> OutputObject for Output<C, T, U, N>
 where
     C: Write<T, U> + Send,
-    T: Send + Sync,
-    U: Send + Sync,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync,
 {
     /// Sends output data
     async fn send(&mut self) -> Result<()> {
+        // the channels are checked before the client is written to
+        let full = self.is_full();
         self.data = (*self.client.lock().await).write();
-        if let Some(data) = &self.data {
-            log::debug!("{} sending", Who::who(self));
-            let futures: Vec<_> = self
-                .tx
-                .iter()
-                .map(|tx| tx.send_async(data.clone()))
-                .collect();
-            join_all(futures)
-                .await
-                .into_iter()
-                .collect::<std::result::Result<Vec<()>, flume::SendError<_>>>()
-                .map_err(|_| flume::SendError(()))?;
-            log::debug!("{} sent", Who::who(self));
-            Ok(())
+        self.dispatch(full).await
+    }
+    async fn hold(&mut self) -> Result<()> {
+        if self.data.is_none() {
+            self.send().await
         } else {
-            for tx in &self.tx {
-                drop(tx);
-            }
-            Err(ActorError::Disconnected(Who::who(self)))
+            let full = self.is_full();
+            self.dispatch(full).await
+        }
+    }
+    async fn send_bootstrap(&mut self) -> Result<()> {
+        match &self.seed {
+            Some(seed) => self.send_data(seed).await,
+            None => self.send().await,
         }
     }
     /// Bootstraps output
     fn bootstrap(&self) -> bool {
         self.bootstrap
     }
+    /// Throttling factor
+    fn throttle(&self) -> Option<usize> {
+        self.throttle
+    }
     fn who(&self) -> String {
         Who::who(self)
     }
@@ -111,4 +303,47 @@ where
     fn len(&self) -> usize {
         self.tx.len()
     }
+    fn channel_id(&self, k: usize) -> usize {
+        self.ids[k]
+    }
+    fn capacity(&self) -> usize {
+        self.tx
+            .first()
+            .and_then(|tx| tx.capacity())
+            .unwrap_or(usize::MAX)
+    }
+    fn resize(&mut self, capacity: usize) -> Vec<(usize, Box<dyn Any + Send>)> {
+        self.tx
+            .iter_mut()
+            .zip(&self.ids)
+            .filter_map(|(tx, &id)| match tx.capacity() {
+                Some(cap) if cap < capacity => {
+                    let (new_tx, rx) = (self.channel)(capacity);
+                    *tx = new_tx;
+                    Some((id, Box::new(rx) as Box<dyn Any + Send>))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+    fn prune(&mut self) {
+        let connected: Vec<bool> = self.tx.iter().map(|tx| !tx.is_disconnected()).collect();
+        let mut keep = connected.iter();
+        self.tx.retain(|_| *keep.next().unwrap());
+        let mut keep = connected.iter();
+        self.ids.retain(|_| *keep.next().unwrap());
+        if let Some((ranges, _)) = self.ranges.as_mut() {
+            let mut keep = connected.iter();
+            ranges.retain(|_| *keep.next().unwrap());
+        }
+    }
+    fn nan_guard(&mut self) {
+        self.nan_guard = true;
+    }
+    fn label(&self) -> Option<String> {
+        self.label.clone()
+    }
+    fn ordered(&self) -> bool {
+        self.ordered
+    }
 }
@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+
+/// Sending half of a channel
+pub type Sender<T> = Box<dyn TransportSender<Item = T>>;
+/// Receiving half of a channel
+pub type Receiver<T> = Box<dyn TransportReceiver<Item = T>>;
+
+/// Error returned by [TransportSender::send] when the receiver has been dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("sending on a disconnected channel")]
+pub struct SendError;
+
+/// Error returned by [TransportReceiver::recv] when all the senders have been dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("receiving on an empty and disconnected channel")]
+pub struct RecvError;
+
+/**
+Channel sender interface
+
+The backpressure warnings of the outputs rely on [is_full](TransportSender::is_full),
+the capacity of the channels resized with [Model::auto_capacity](crate::model::Model::auto_capacity)
+on [capacity](TransportSender::capacity) and the outputs stop sending to the inputs of terminated actors
+according to [is_disconnected](TransportSender::is_disconnected).
+*/
+#[async_trait]
+pub trait TransportSender: Send + Sync {
+    /// Type of the data sent through the channel
+    type Item;
+    /// Sends `data`, waiting for some room in the channel if it is full
+    async fn send(&self, data: Self::Item) -> Result<(), SendError>;
+    /// Checks if the channel is full
+    fn is_full(&self) -> bool;
+    /// Returns the channel capacity, [None] if unbounded
+    fn capacity(&self) -> Option<usize>;
+    /// Checks if the receiver has been dropped
+    fn is_disconnected(&self) -> bool;
+}
+
+/// Channel receiver interface
+#[async_trait]
+pub trait TransportReceiver: Send + Sync {
+    /// Type of the data received from the channel
+    type Item;
+    /// Receives data, waiting for some data if the channel is empty
+    async fn recv(&mut self) -> Result<Self::Item, RecvError>;
+    /// Receives data if the channel is not empty
+    fn try_recv(&mut self) -> Option<Self::Item>;
+    /// Checks if all the senders have been dropped
+    fn is_disconnected(&self) -> bool;
+}
+
+/**
+Channels factory
+
+The channels between the outputs and the inputs of the [Actor](crate::Actor)s are created with
+the [Transport] selected with [transport](crate::AddOuput::transport), by default the [Flume] transport.
+
+# Example
+A model running over [tokio](tokio::sync::mpsc) channels
+```
+# tokio_test::block_on(async {
+use async_trait::async_trait;
+use dos_actors::{
+    io::{RecvError, SendError, Transport, TransportReceiver, TransportSender},
+    prelude::*,
+};
+use tokio::sync::mpsc;
+struct Tx<T>(mpsc::Sender<T>);
+#[async_trait]
+impl<T: Send> TransportSender for Tx<T> {
+    type Item = T;
+    async fn send(&self, data: T) -> Result<(), SendError> {
+        self.0.send(data).await.map_err(|_| SendError)
+    }
+    fn is_full(&self) -> bool {
+        self.0.capacity() == 0
+    }
+    fn capacity(&self) -> Option<usize> {
+        Some(self.0.max_capacity())
+    }
+    fn is_disconnected(&self) -> bool {
+        self.0.is_closed()
+    }
+}
+struct Rx<T>(mpsc::Receiver<T>);
+#[async_trait]
+impl<T: Send> TransportReceiver for Rx<T> {
+    type Item = T;
+    async fn recv(&mut self) -> Result<T, RecvError> {
+        self.0.recv().await.ok_or(RecvError)
+    }
+    fn try_recv(&mut self) -> Option<T> {
+        self.0.try_recv().ok()
+    }
+    fn is_disconnected(&self) -> bool {
+        self.0.is_closed() && self.0.is_empty()
+    }
+}
+struct Tokio;
+impl Transport for Tokio {
+    type Sender<T: 'static + Send + Sync> = Tx<T>;
+    type Receiver<T: 'static + Send + Sync> = Rx<T>;
+    fn bounded<T: 'static + Send + Sync>(capacity: usize) -> (Tx<T>, Rx<T>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        (Tx(tx), Rx(rx))
+    }
+    fn unbounded<T: 'static + Send + Sync>() -> (Tx<T>, Rx<T>) {
+        Self::bounded(1 << 16)
+    }
+}
+enum Sig {};
+let mut source: Initiator<_> = Signals::new(1, 100).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source
+    .add_output()
+    .transport::<Tokio>()
+    .build::<Vec<f64>, Sig>()
+    .into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+assert_eq!(logging.lock().await.len(), 100);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub trait Transport: 'static {
+    type Sender<T: 'static + Send + Sync>: TransportSender<Item = T> + 'static;
+    type Receiver<T: 'static + Send + Sync>: TransportReceiver<Item = T> + 'static;
+    /// Creates a channel of `capacity`
+    fn bounded<T: 'static + Send + Sync>(capacity: usize) -> (Self::Sender<T>, Self::Receiver<T>);
+    /// Creates a channel of unlimited capacity
+    fn unbounded<T: 'static + Send + Sync>() -> (Self::Sender<T>, Self::Receiver<T>);
+}
+
+// Creates a channel of `capacity` with the transport `Tr`, unbounded if `capacity` is [usize::MAX]
+pub(crate) fn channel<Tr: Transport, T: 'static + Send + Sync>(
+    capacity: usize,
+) -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = transport_channel::<Tr, T>(capacity);
+    (tx, Box::new(rx))
+}
+// Creates a channel of `capacity` with the transport `Tr`, the receiver keeps the type of the transport
+pub(crate) fn transport_channel<Tr: Transport, T: 'static + Send + Sync>(
+    capacity: usize,
+) -> (Sender<T>, Tr::Receiver<T>) {
+    if capacity == usize::MAX {
+        let (tx, rx) = Tr::unbounded::<T>();
+        (Box::new(tx), rx)
+    } else {
+        let (tx, rx) = Tr::bounded::<T>(capacity);
+        (Box::new(tx), rx)
+    }
+}
+
+/// [flume] channels transport
+pub struct Flume;
+impl Transport for Flume {
+    type Sender<T: 'static + Send + Sync> = flume::Sender<T>;
+    type Receiver<T: 'static + Send + Sync> = flume::Receiver<T>;
+    fn bounded<T: 'static + Send + Sync>(capacity: usize) -> (Self::Sender<T>, Self::Receiver<T>) {
+        flume::bounded(capacity)
+    }
+    fn unbounded<T: 'static + Send + Sync>() -> (Self::Sender<T>, Self::Receiver<T>) {
+        flume::unbounded()
+    }
+}
+#[async_trait]
+impl<T: Send> TransportSender for flume::Sender<T> {
+    type Item = T;
+    async fn send(&self, data: T) -> Result<(), SendError> {
+        self.send_async(data).await.map_err(|_| SendError)
+    }
+    fn is_full(&self) -> bool {
+        flume::Sender::is_full(self)
+    }
+    fn capacity(&self) -> Option<usize> {
+        flume::Sender::capacity(self)
+    }
+    fn is_disconnected(&self) -> bool {
+        flume::Sender::is_disconnected(self)
+    }
+}
+#[async_trait]
+impl<T: Send> TransportReceiver for flume::Receiver<T> {
+    type Item = T;
+    async fn recv(&mut self) -> Result<T, RecvError> {
+        self.recv_async().await.map_err(|_| RecvError)
+    }
+    fn try_recv(&mut self) -> Option<T> {
+        flume::Receiver::try_recv(self).ok()
+    }
+    fn is_disconnected(&self) -> bool {
+        flume::Receiver::is_disconnected(self)
+    }
+}
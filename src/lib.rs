@@ -75,32 +75,45 @@ The crates provides a minimal set of default functionalities that can be augment
  - **noise** : enables the [rand] and [rand_distr] crates
  - **lom** : enables the Linear Optical Model crate [gmt-lom](https://docs.rs/gmt_lom) [client](crate::clients::lom)
  - **ceo** : enables the CEO binder/wrapper crate [crseo](https://docs.rs/crseo) [client](crate::clients::ceo)
+ - **serde** : enables the JSON [model configuration](crate::config), the [Signals](crate::clients::Signals) and the model [topology](crate::model::Topology) serialization
 */
 
-use std::{any::type_name, sync::Arc};
+use std::{
+    any::type_name,
+    marker::PhantomData,
+    ops::Range,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::sync::Mutex;
 
 pub mod actor;
 pub mod clients;
+#[cfg(feature = "serde")]
+pub mod config;
 pub mod io;
 pub mod model;
+pub mod sweep;
+#[cfg(feature = "test-utils")]
+pub mod testing;
 #[doc(inline)]
-pub use actor::{Actor, Initiator, Task, Terminator, Update};
+pub use actor::{Actor, Initiator, SimClock, Task, Terminator, Update};
 
 #[derive(thiserror::Error, Debug)]
 pub enum ActorError {
     #[error("receiver disconnected")]
-    DropRecv(#[from] flume::RecvError),
+    DropRecv(#[from] io::RecvError),
     #[error("sender disconnected")]
-    DropSend(#[from] flume::SendError<()>),
+    DropSend(#[from] io::SendError),
     #[error("no new data produced")]
     NoData,
     #[error("no inputs defined")]
     NoInputs,
     #[error("no outputs defined")]
     NoOutputs,
-    #[error("no client defined")]
-    NoClient,
     #[error("output {0} dropped")]
     Disconnected(String),
     #[error("{0} has some inputs but inputs rate is zero")]
@@ -111,6 +124,24 @@ pub enum ActorError {
     SomeOutputsZeroRate(String),
     #[error("{0} has no outputs but a positive outputs rate")]
     NoOutputsPositiveRate(String),
+    #[error("the bootstrapped output {0} cannot be throttled")]
+    ThrottledBootstrap(String),
+    #[error("output {0} produced a non-finite value")]
+    NonFinite(String),
+    #[error("{data} length mismatch: expected {expected}, got {got}")]
+    LengthMismatch {
+        expected: usize,
+        got: usize,
+        data: String,
+    },
+    #[error("{data} out of order: step {step} received after step {previous}")]
+    OutOfOrder {
+        step: usize,
+        previous: usize,
+        data: String,
+    },
+    #[error("{0} client failed: {1}")]
+    Client(String, String),
 }
 pub type Result<R> = std::result::Result<R, ActorError>;
 
@@ -123,12 +154,10 @@ where
     where
         Self: Sized;
 }
-impl<T, U, CI, CO, const N: usize, const NO: usize, const NI: usize> IntoInputs<CI, N, NO>
-    for (
-        &Actor<CO, NI, NO>,
-        Vec<flume::Receiver<Arc<io::Data<T, U>>>>,
-    )
+impl<T, U, R, CI, CO, const N: usize, const NO: usize, const NI: usize> IntoInputs<CI, N, NO>
+    for (&Actor<CO, NI, NO>, Vec<R>)
 where
+    R: 'static + io::TransportReceiver<Item = Arc<io::Data<T, U>>>,
     T: 'static + Send + Sync,
     U: 'static + Send + Sync,
     CI: 'static + Update + Send + io::Read<T, U>,
@@ -137,26 +166,285 @@ where
     /// Creates a new input for 'actor' from the last 'Receiver'
     fn into_input(mut self, actor: &mut Actor<CI, NO, N>) -> Self {
         if let Some(recv) = self.1.pop() {
-            actor.add_input(recv)
+            let id = self.0.channel_id(self.1.len());
+            actor.add_input(Box::new(recv), id, self.0.ordered_output())
         }
         self
     }
 }
 
+/**
+Assign inputs to actors with a validation of the length of the data
+
+The length of the data received by the input is checked against the length `n` given at connection time,
+the actor loop ends with the error [ActorError::LengthMismatch] if the lengths differ.
+
+# Example
+```
+# tokio_test::block_on(async {
+use dos_actors::{prelude::*, ActorError};
+enum Sig {};
+let mut source: Initiator<_> = Signals::new(41, 10).into();
+let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+source
+    .add_output()
+    .build::<Vec<f64>, Sig>()
+    .into_sized_input(&mut sink, 42);
+source.step().await.unwrap();
+match sink.step().await {
+    Err(ActorError::LengthMismatch {
+        expected, got, data,
+    }) => {
+        assert_eq!((expected, got), (42, 41));
+        assert!(data.ends_with("Sig"));
+    }
+    _ => panic!("expected a length mismatch"),
+}
+# });
+```
+*/
+pub trait IntoSizedInputs<CI, const N: usize, const NO: usize>
+where
+    CI: Update + Send,
+{
+    fn into_sized_input(self, actor: &mut Actor<CI, NO, N>, n: usize) -> Self
+    where
+        Self: Sized;
+}
+impl<T, U, R, CI, CO, const N: usize, const NO: usize, const NI: usize> IntoSizedInputs<CI, N, NO>
+    for (&Actor<CO, NI, NO>, Vec<R>)
+where
+    R: 'static + io::TransportReceiver<Item = Arc<io::Data<Vec<T>, U>>>,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync,
+    CI: 'static + Update + Send + io::Read<Vec<T>, U>,
+    CO: 'static + Update + Send + io::Write<Vec<T>, U>,
+{
+    /// Creates a new input for 'actor' from the last 'Receiver' expecting data of length `n`
+    fn into_sized_input(mut self, actor: &mut Actor<CI, NO, N>, n: usize) -> Self {
+        if let Some(recv) = self.1.pop() {
+            let id = self.0.channel_id(self.1.len());
+            actor.add_sized_input(Box::new(recv), id, n, self.0.ordered_output())
+        }
+        self
+    }
+}
+
+/**
+Assign inputs to actors through a rate transition
+
+The output is connected to a new [Sampler](clients::Sampler) actor which output is connected to the input of `actor`,
+the [Sampler](clients::Sampler) actor inputs rate is the outputs rate of the producer and its outputs rate is the inputs rate of `actor`,
+so the data is decimated if `actor` runs at a lower rate than the producer or it is sampled-and-hold if `actor` runs at a higher rate.
+The [Sampler](clients::Sampler) actor is returned and must be added to the [Model](model::Model), it is named after the producer and the consumer.
+See also the [rate_transition] macro.
+
+# Example
+A 1kHz source logged at 200Hz
+```
+# tokio_test::block_on(async {
+use dos_actors::prelude::*;
+enum Sig {};
+let mut source: Initiator<_> = (Signals::new(1, 1000).signals(Signal::Ramp { a: 1., b: 0. }), "source").into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_, 5>::new(logging.clone());
+let sampler = source
+    .add_output()
+    .build::<Vec<f64>, Sig>()
+    .into_sampled_input(&mut sink);
+assert!(sampler.as_plain().client.starts_with("source => "));
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let logging = logging.lock().await;
+assert_eq!(logging.len(), 200);
+assert_eq!(logging[1], 9.);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub trait IntoSampledInputs<T, U, const NO: usize>
+where
+    T: Send + Sync,
+    U: Send + Sync,
+{
+    fn into_sampled_input<CI, const NI: usize, const N: usize>(
+        self,
+        actor: &mut Actor<CI, NI, N>,
+    ) -> Actor<clients::Sampler<T, U>, NO, NI>
+    where
+        CI: 'static + Update + Send + io::Read<T, U>;
+}
+impl<T, U, R, CO, const NI: usize, const NO: usize> IntoSampledInputs<T, U, NO>
+    for (&Actor<CO, NI, NO>, Vec<R>)
+where
+    R: 'static + io::TransportReceiver<Item = Arc<io::Data<T, U>>>,
+    T: 'static + Send + Sync + Clone + Default,
+    U: 'static + Send + Sync,
+    CO: 'static + Update + Send + io::Write<T, U>,
+{
+    /// Creates a new [Sampler](clients::Sampler) actor between the last 'Receiver' and `actor`
+    fn into_sampled_input<CI, const NC: usize, const N: usize>(
+        self,
+        actor: &mut Actor<CI, NC, N>,
+    ) -> Actor<clients::Sampler<T, U>, NO, NC>
+    where
+        CI: 'static + Update + Send + io::Read<T, U>,
+    {
+        let name = format!(
+            "{} => {}",
+            self.0.as_plain().client,
+            actor.as_plain().client
+        );
+        let mut sampler: Actor<clients::Sampler<T, U>, NO, NC> =
+            (clients::Sampler::<T, U>::default(), name).into();
+        self.into_input(&mut sampler);
+        sampler.add_output().build::<T, U>().into_input(actor);
+        sampler
+    }
+}
+
+/**
+Assign optional inputs to actors
+
+The client of an optional input reads [None] if no data has been received within the `timeout`
+and it must implement the [ReadOption](io::ReadOption) trait.
+
+# Example
+A producer skipping some steps: every 4th sample, the producer waits for the consumer
+to have read [None] after the previous sample before sending the sample
+```
+use dos_actors::{io, prelude::*, Update};
+use std::{
+    sync::{mpsc, Arc},
+    time::Duration,
+};
+struct Skipper(usize, mpsc::Receiver<usize>);
+impl Update for Skipper {
+    fn update(&mut self) {
+        if self.0 % 4 == 3 {
+            // waiting for the consumer to time out after reading the previous sample
+            tokio::task::block_in_place(|| while self.1.recv().unwrap() < self.0 {});
+        }
+    }
+}
+impl<U> io::Write<Vec<f64>, U> for Skipper {
+    fn write(&mut self) -> Option<Arc<io::Data<Vec<f64>, U>>> {
+        if self.0 == 12 {
+            return None;
+        }
+        self.0 += 1;
+        Some(Arc::new(io::Data::new(vec![(self.0 - 1) as f64])))
+    }
+}
+struct Watcher(Vec<Option<f64>>, mpsc::Sender<usize>);
+impl Update for Watcher {}
+impl<U> io::ReadOption<Vec<f64>, U> for Watcher {
+    fn read_option(&mut self, data: Option<Arc<io::Data<Vec<f64>, U>>>) {
+        if data.is_none() {
+            let _ = self.1.send(self.0.iter().flatten().count());
+        }
+        self.0.push(data.map(|data| data[0]));
+    }
+}
+enum Sig {};
+tokio::runtime::Builder::new_multi_thread()
+    .worker_threads(2)
+    .enable_time()
+    .build()
+    .unwrap()
+    .block_on(async {
+        let (tx, rx) = mpsc::channel();
+        let mut source: Initiator<_> = Skipper(0, rx).into();
+        let watcher = Watcher(Vec::new(), tx).into_arcx();
+        let mut sink = Terminator::<_>::new(watcher.clone());
+        source
+            .add_output()
+            .build::<Vec<f64>, Sig>()
+            .into_optional_input(&mut sink, Duration::from_millis(40));
+        Model::new(vec![Box::new(source), Box::new(sink)])
+            .check()?
+            .run()
+            .wait()
+            .await?;
+        let samples = &watcher.lock().await.0;
+        assert_eq!(
+            samples.iter().flatten().cloned().collect::<Vec<f64>>(),
+            (0..12).map(|x| x as f64).collect::<Vec<f64>>()
+        );
+        // each skipped sample is preceded by at least one None
+        for i in [3., 7., 11.] {
+            let k = samples.iter().position(|x| *x == Some(i)).unwrap();
+            assert_eq!(samples[k - 1], None);
+        }
+        Ok::<(), dos_actors::model::ModelError>(())
+    })?;
+# Ok::<(), dos_actors::model::ModelError>(())
+```
+*/
+pub trait IntoOptionalInputs<CI, const N: usize, const NO: usize>
+where
+    CI: Update + Send,
+{
+    fn into_optional_input(self, actor: &mut Actor<CI, NO, N>, timeout: Duration) -> Self
+    where
+        Self: Sized;
+}
+impl<T, U, R, CI, CO, const N: usize, const NO: usize, const NI: usize>
+    IntoOptionalInputs<CI, N, NO> for (&Actor<CO, NI, NO>, Vec<R>)
+where
+    R: 'static + io::TransportReceiver<Item = Arc<io::Data<T, U>>>,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync,
+    CI: 'static + Update + Send + io::ReadOption<T, U>,
+    CO: 'static + Update + Send + io::Write<T, U>,
+{
+    /// Creates a new optional input for 'actor' from the last 'Receiver'
+    fn into_optional_input(mut self, actor: &mut Actor<CI, NO, N>, timeout: Duration) -> Self {
+        if let Some(recv) = self.1.pop() {
+            let id = self.0.channel_id(self.1.len());
+            actor.add_optional_input(Box::new(recv), id, timeout, self.0.ordered_output())
+        }
+        self
+    }
+}
+
+// Unique identifier of the channels between actors
+static CHANNEL_ID: AtomicUsize = AtomicUsize::new(0);
+
 /// Actor outputs builder
-pub struct ActorOutputBuilder {
+///
+/// The output channels are created with the [io::Transport] `Tr`,
+/// the output is bootstrapped with the [BootstrapValue] `B` and demultiplexed with the [Demultiplex] `D`
+pub struct ActorOutputBuilder<Tr: io::Transport = io::Flume, B = (), D = ()> {
     capacity: Vec<usize>,
     bootstrap: bool,
+    bootstrap_value: B,
+    throttle: Option<(f64, f64)>,
+    backpressure: usize,
+    ranges: D,
+    label: Option<String>,
+    ordered: bool,
+    transport: PhantomData<fn() -> Tr>,
 }
-impl Default for ActorOutputBuilder {
+impl<Tr: io::Transport> Default for ActorOutputBuilder<Tr> {
     fn default() -> Self {
         Self {
             capacity: Vec::new(),
             bootstrap: false,
+            bootstrap_value: (),
+            throttle: None,
+            backpressure: 100,
+            ranges: (),
+            label: None,
+            ordered: false,
+            transport: PhantomData,
         }
     }
 }
-impl ActorOutputBuilder {
+impl<Tr: io::Transport> ActorOutputBuilder<Tr> {
     /// Creates a new actor output builder multiplexed `n` times
     pub fn new(n: usize) -> Self {
         Self {
@@ -166,34 +454,459 @@ impl ActorOutputBuilder {
     }
 }
 
+/// Value of a bootstrapped output of type `T`
+pub trait BootstrapValue<T> {
+    /// Returns the data sent before the actor loop starts, if any
+    fn into_data(self) -> Option<T>;
+}
+/// No bootstrap value, the bootstrapped data is written by the client
+impl<T> BootstrapValue<T> for () {
+    fn into_data(self) -> Option<T> {
+        None
+    }
+}
+/// Bootstrap value set with [AddOuput::bootstrap_value]
+pub struct Seed<T>(T);
+impl<T> BootstrapValue<T> for Seed<T> {
+    fn into_data(self) -> Option<T> {
+        Some(self.0)
+    }
+}
+
+/// Receivers of the channels of an output of data `T` and UID `U` created with the [io::Transport] `Tr`
+pub type OutputReceivers<Tr, T, U> = Vec<<Tr as io::Transport>::Receiver<Arc<io::Data<T, U>>>>;
+
+/// Actor with one of its outputs being built
+pub type OutputBuilding<'a, C, Tr, B, D, const NI: usize, const NO: usize> =
+    (&'a mut Actor<C, NI, NO>, ActorOutputBuilder<Tr, B, D>);
+
+/// Demultiplexing of an output of type `T`
+pub trait Demultiplex<T> {
+    /// Returns the data range sent to each input with the function slicing the data, if the output is demultiplexed
+    fn into_ranges(self) -> Option<(Vec<Range<usize>>, io::Slice<T>)>;
+}
+/// No demultiplexing, the same data is sent to all the inputs
+impl<T> Demultiplex<T> for () {
+    fn into_ranges(self) -> Option<(Vec<Range<usize>>, io::Slice<T>)> {
+        None
+    }
+}
+/// Ranges set with [AddOuput::demultiplex], only `Vec<f64>` outputs can be demultiplexed
+pub struct Ranges(Vec<Range<usize>>);
+impl Demultiplex<Vec<f64>> for Ranges {
+    fn into_ranges(self) -> Option<(Vec<Range<usize>>, io::Slice<Vec<f64>>)> {
+        Some((self.0, |data, range| {
+            data.get(range).map(<[f64]>::to_vec).ok_or(data.len())
+        }))
+    }
+}
+
 /// Actor add output interface
 pub trait AddOuput<'a, C, const NI: usize, const NO: usize>
 where
     C: 'static + Update + Send,
 {
-    /// Sets the channel to unbounded
+    /// Transport of the output channels
+    type Transport: io::Transport;
+    /// Bootstrap value of the output
+    type Value;
+    /// Demultiplexing of the output
+    type Ranges;
+    /**
+    Sets the channel to unbounded
+
+    When the producer stops, the data still queued in the channel is received before the consumer stops.
+
+    # Example
+    A slow consumer logging all the samples of a source that has already stopped
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{clients::Sink, prelude::*};
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+    enum Sig {};
+    let n_step = 100;
+    let mut source: Initiator<_> = Signals::new(1, n_step).into();
+    let samples = Arc::new(Mutex::new(0usize));
+    let count = samples.clone();
+    let mut sink: Terminator<_> = Sink::<Sig>::new(move |_, _| {
+        std::thread::sleep(Duration::from_micros(100));
+        *count.lock().unwrap() += 1;
+    })
+    .into();
+    source
+        .add_output()
+        .unbounded()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert_eq!(*samples.lock().unwrap(), n_step);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
     fn unbounded(self) -> Self;
     /// Flags the output to be bootstrapped
     fn bootstrap(self) -> Self;
+    /**
+    Bootstraps the output with `data` instead of the data written by the client
+
+    `data` is only sent before the actor loop starts, the subsequent samples are written by the client.
+    The type of `data` must be the type `T` of the output data given to [build](AddOuput::build),
+    otherwise the output does not compile:
+    ```compile_fail
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut sampler: Actor<_> = Sampler::<Vec<f64>, Sig>::default().into();
+    sampler.add_output().bootstrap_value(42f64).build::<Vec<f64>, Sig>();
+    ```
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 5)
+        .signals(Signal::Ramp { a: 1., b: 0. })
+        .into();
+    let mut sampler: Actor<_> = Sampler::<Vec<f64>, Sig>::default().into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+    sampler
+        .add_output()
+        .bootstrap_value(vec![42f64])
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert_eq!(**logging.lock().await, vec![42., 0., 1., 2., 3., 4.]);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    fn bootstrap_value<T: 'static + Send>(
+        self,
+        data: T,
+    ) -> OutputBuilding<'a, C, Self::Transport, Seed<T>, Self::Ranges, NI, NO>;
     /// Multiplexes the output `n` times
     fn multiplex(self, n: usize) -> Self;
-    /// Builds the new output
-    fn build<T, U>(
+    /**
+    Demultiplexes the output into `ranges.len()` outputs
+
+    The output data must be a `Vec<f64>`,
+    the `k`th input the output is connected to receives the slice `ranges[k]` of the data.
+    The actor loop ends with the error [ActorError::LengthMismatch] if the data ends before one of the ranges.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{clients::Source, prelude::*};
+    enum Sig {};
+    let mut source: Initiator<_> = Source::<Sig>::new(|step| {
+        (step < 10).then(|| (0..84).map(|i| (i + step) as f64).collect())
+    })
+    .into();
+    let first_logging = Logging::<f64>::default().into_arcx();
+    let mut first_sink = Terminator::<_>::new(first_logging.clone());
+    let second_logging = Logging::<f64>::default().into_arcx();
+    let mut second_sink = Terminator::<_>::new(second_logging.clone());
+    source
+        .add_output()
+        .demultiplex(vec![0..42, 42..84])
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut first_sink)
+        .into_input(&mut second_sink);
+    Model::new(vec![
+        Box::new(source),
+        Box::new(first_sink),
+        Box::new(second_sink),
+    ])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+    let (first, second) = (first_logging.lock().await, second_logging.lock().await);
+    assert_eq!((first.len(), first.n_data()), (10, 42));
+    assert_eq!((second.len(), second.n_data()), (10, 42));
+    for (step, (first, second)) in first.chunks().zip(second.chunks()).enumerate() {
+        assert_eq!(first[0], step as f64);
+        assert_eq!(second[0], (42 + step) as f64);
+    }
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+
+    A data shorter than the ranges is an error
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{prelude::*, ActorError};
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(41, 10).into();
+    let mut first_sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    let mut second_sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .demultiplex(vec![0..21, 21..42])
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut first_sink)
+        .into_input(&mut second_sink);
+    match source.step().await {
+        Err(ActorError::LengthMismatch { expected, got, .. }) => {
+            assert_eq!((expected, got), (42, 41))
+        }
+        _ => panic!("expected a length mismatch"),
+    }
+    # });
+    ```
+
+    Only `Vec<f64>` outputs can be demultiplexed
+    ```compile_fail
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut sampler: Actor<_> = Sampler::<Vec<f32>, Sig>::default().into();
+    sampler
+        .add_output()
+        .demultiplex(vec![0..21, 21..42])
+        .build::<Vec<f32>, Sig>();
+    ```
+    */
+    fn demultiplex(
         self,
-    ) -> (
-        &'a Actor<C, NI, NO>,
-        Vec<flume::Receiver<Arc<io::Data<T, U>>>>,
-    )
+        ranges: Vec<Range<usize>>,
+    ) -> OutputBuilding<'a, C, Self::Transport, Self::Value, Ranges, NI, NO>;
+    /**
+    Caps the output sending rate to `rate_hz`
+
+    Given the simulation sampling frequency `sampling_frequency_hz`, only every k-th sample
+    produced by the client is sent and the others are dropped, independently of the outputs rate `NO`.
+    A throttled output can only be connected to a [Terminator] and cannot be bootstrapped (see [Model::check](crate::model::Model::check)).
+
+    # Example
+    A 1kHz source feeding a sink at 30Hz
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 1000).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source
+        .add_output()
+        .throttle_hz(1000., 30.)
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    assert!((29..=31).contains(&logging.lock().await.len()));
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    fn throttle_hz(self, sampling_frequency_hz: f64, rate_hz: f64) -> Self;
+    /**
+    Warns once if the output is blocked on a full channel for `k` consecutive steps (default: 100)
+
+    A producer persistently blocked on a full channel indicates that the consumer is too slow.
+    The channels are checked before the client output is written and the warning is issued with [log::warn].
+
+    # Example
+    A consumer waiting, after reading a sample, for the producer to write the sample after next
+    ```
+    use dos_actors::{io, prelude::*, Update};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+    static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    struct Logger;
+    impl log::Log for Logger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Warn
+        }
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                WARNINGS.lock().unwrap().push(record.args().to_string());
+            }
+        }
+        fn flush(&self) {}
+    }
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+    const N: usize = 50;
+    // Counts the writes, including the final one returning None
+    struct Counter(Arc<AtomicUsize>);
+    impl Update for Counter {}
+    impl<U> io::Write<Vec<f64>, U> for Counter {
+        fn write(&mut self) -> Option<Arc<io::Data<Vec<f64>, U>>> {
+            let i = self.0.fetch_add(1, Ordering::SeqCst);
+            (i < N).then(|| Arc::new(io::Data::new(vec![i as f64])))
+        }
+    }
+    struct Slow(Arc<AtomicUsize>, usize);
+    impl Update for Slow {
+        fn update(&mut self) {
+            tokio::task::block_in_place(|| {
+                while self.0.load(Ordering::SeqCst) < (self.1 + 2).min(N + 1) {
+                    std::thread::sleep(Duration::from_micros(100));
+                }
+            });
+        }
+    }
+    impl<U> io::Read<Vec<f64>, U> for Slow {
+        fn read(&mut self, _data: Arc<io::Data<Vec<f64>, U>>) {
+            self.1 += 1;
+        }
+    }
+    enum Sig {};
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let n_write = Arc::new(AtomicUsize::new(0));
+            let mut source: Initiator<_> = Counter(n_write.clone()).into();
+            let mut sink: Terminator<_> = Slow(n_write, 0).into();
+            source
+                .add_output()
+                .backpressure(10)
+                .build::<Vec<f64>, Sig>()
+                .into_input(&mut sink);
+            Model::new(vec![Box::new(source), Box::new(sink)])
+                .check()?
+                .run()
+                .wait()
+                .await?;
+            Ok::<(), dos_actors::model::ModelError>(())
+        })?;
+    let warnings = WARNINGS.lock().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("Sig: blocked on a full channel for 10 consecutive steps"));
+    assert!(warnings[0].starts_with(std::any::type_name::<Counter>()));
+    # Ok::<(), dos_actors::model::ModelError>(())
+    ```
+    */
+    fn backpressure(self, k: usize) -> Self;
+    /**
+    Labels the output
+
+    The label is written next to the output in the [Model](model::Model) flowchart and in the logs and error messages about the output,
+    telling apart outputs of the same data type.
+
+    # Example
+    ```
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .label("hardpoint forces")
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(sink)]);
+    let dot = model.graph().unwrap().to_string();
+    assert!(dot.contains(r#"label="hardpoint forces""#));
+    ```
+    */
+    fn label<S: Into<String>>(self, label: S) -> Self;
+    /**
+    Validates the order of the data at the inputs the output is connected to
+
+    The steps the data are [stamped](io::Data::stamped) with must be strictly increasing,
+    the actor loop of the consumer ends with the error [ActorError::OutOfOrder] on the first duplicated or reordered step.
+    Unstamped data are not checked.
+    The validation applies to all the inputs the output is connected to:
+    [regular](IntoInputs), [sized](IntoSizedInputs), [optional](IntoOptionalInputs) or [sampled](IntoSampledInputs) inputs.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{io, prelude::*, ActorError, Update};
+    use std::sync::Arc;
+    struct Stamps(Vec<usize>);
+    impl Update for Stamps {}
+    impl<U> io::Write<Vec<f64>, U> for Stamps {
+        fn write(&mut self) -> Option<Arc<io::Data<Vec<f64>, U>>> {
+            let step = self.0.remove(0);
+            Some(Arc::new(io::Data::new(vec![step as f64]).stamped(step)))
+        }
+    }
+    enum Sig {};
+    // in order
+    let mut source: Initiator<_> = Stamps(vec![1, 2, 3]).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .ordered()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    for _ in 0..3 {
+        source.step().await.unwrap();
+        sink.step().await.unwrap();
+    }
+    // out of order, with the size of the data validated as well
+    let mut source: Initiator<_> = Stamps(vec![1, 3, 2]).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .ordered()
+        .build::<Vec<f64>, Sig>()
+        .into_sized_input(&mut sink, 1);
+    for _ in 0..2 {
+        source.step().await.unwrap();
+        sink.step().await.unwrap();
+    }
+    source.step().await.unwrap();
+    match sink.step().await {
+        Err(ActorError::OutOfOrder { step, previous, data }) => {
+            assert_eq!((step, previous), (2, 3));
+            assert!(data.ends_with("Sig"));
+        }
+        _ => panic!("expected an out of order step"),
+    }
+    # });
+    ```
+    */
+    fn ordered(self) -> Self;
+    /// Creates the output channels with the [io::Transport] `Tr` instead of the [io::Flume] default
+    fn transport<Tr: io::Transport>(
+        self,
+    ) -> OutputBuilding<'a, C, Tr, Self::Value, Self::Ranges, NI, NO>;
+    /// Builds the new output
+    fn build<T, U>(self) -> (&'a Actor<C, NI, NO>, OutputReceivers<Self::Transport, T, U>)
     where
         C: io::Write<T, U>,
+        Self::Value: BootstrapValue<T>,
+        Self::Ranges: Demultiplex<T>,
         T: 'static + Send + Sync,
         U: 'static + Send + Sync;
 }
-impl<'a, C, const NI: usize, const NO: usize> AddOuput<'a, C, NI, NO>
-    for (&'a mut Actor<C, NI, NO>, ActorOutputBuilder)
+impl<'a, C, Tp, B, D, const NI: usize, const NO: usize> AddOuput<'a, C, NI, NO>
+    for OutputBuilding<'a, C, Tp, B, D, NI, NO>
 where
+    Tp: io::Transport,
     C: 'static + Update + Send,
 {
+    type Transport = Tp;
+    type Value = B;
+    type Ranges = D;
     fn unbounded(self) -> Self {
         let n = self.1.capacity.len();
         (
@@ -213,6 +926,26 @@ where
             },
         )
     }
+    fn bootstrap_value<T: 'static + Send>(
+        self,
+        data: T,
+    ) -> OutputBuilding<'a, C, Tp, Seed<T>, D, NI, NO> {
+        let (actor, builder) = self;
+        (
+            actor,
+            ActorOutputBuilder {
+                capacity: builder.capacity,
+                bootstrap: true,
+                bootstrap_value: Seed(data),
+                throttle: builder.throttle,
+                backpressure: builder.backpressure,
+                ranges: builder.ranges,
+                label: builder.label,
+                ordered: builder.ordered,
+                transport: PhantomData,
+            },
+        )
+    }
     fn multiplex(self, n: usize) -> Self {
         (
             self.0,
@@ -222,14 +955,84 @@ where
             },
         )
     }
-    fn build<T, U>(
+    fn demultiplex(
         self,
-    ) -> (
-        &'a Actor<C, NI, NO>,
-        Vec<flume::Receiver<Arc<io::Data<T, U>>>>,
-    )
+        ranges: Vec<Range<usize>>,
+    ) -> OutputBuilding<'a, C, Tp, B, Ranges, NI, NO> {
+        let (actor, builder) = self;
+        (
+            actor,
+            ActorOutputBuilder {
+                capacity: vec![builder.capacity[0]; ranges.len()],
+                bootstrap: builder.bootstrap,
+                bootstrap_value: builder.bootstrap_value,
+                throttle: builder.throttle,
+                backpressure: builder.backpressure,
+                ranges: Ranges(ranges),
+                label: builder.label,
+                ordered: builder.ordered,
+                transport: PhantomData,
+            },
+        )
+    }
+    fn throttle_hz(self, sampling_frequency_hz: f64, rate_hz: f64) -> Self {
+        (
+            self.0,
+            ActorOutputBuilder {
+                throttle: Some((sampling_frequency_hz, rate_hz)),
+                ..self.1
+            },
+        )
+    }
+    fn backpressure(self, k: usize) -> Self {
+        (
+            self.0,
+            ActorOutputBuilder {
+                backpressure: k,
+                ..self.1
+            },
+        )
+    }
+    fn label<S: Into<String>>(self, label: S) -> Self {
+        (
+            self.0,
+            ActorOutputBuilder {
+                label: Some(label.into()),
+                ..self.1
+            },
+        )
+    }
+    fn ordered(self) -> Self {
+        (
+            self.0,
+            ActorOutputBuilder {
+                ordered: true,
+                ..self.1
+            },
+        )
+    }
+    fn transport<Tr: io::Transport>(self) -> OutputBuilding<'a, C, Tr, B, D, NI, NO> {
+        let (actor, builder) = self;
+        (
+            actor,
+            ActorOutputBuilder {
+                capacity: builder.capacity,
+                bootstrap: builder.bootstrap,
+                bootstrap_value: builder.bootstrap_value,
+                throttle: builder.throttle,
+                backpressure: builder.backpressure,
+                ranges: builder.ranges,
+                label: builder.label,
+                ordered: builder.ordered,
+                transport: PhantomData,
+            },
+        )
+    }
+    fn build<T, U>(self) -> (&'a Actor<C, NI, NO>, OutputReceivers<Tp, T, U>)
     where
         C: 'static + Update + Send + io::Write<T, U>,
+        B: BootstrapValue<T>,
+        D: Demultiplex<T>,
         T: 'static + Send + Sync,
         U: 'static + Send + Sync,
     {
@@ -237,18 +1040,38 @@ where
         let (actor, builder) = self;
         let mut txs = vec![];
         let mut rxs = vec![];
+        let mut ids = vec![];
         for &cap in &builder.capacity {
-            let (tx, rx) = if cap == usize::MAX {
-                flume::unbounded::<S<T, U>>()
-            } else {
-                flume::bounded::<S<T, U>>(cap)
-            };
+            let (tx, rx) = io::transport_channel::<Tp, S<T, U>>(cap);
             txs.push(tx);
             rxs.push(rx);
+            ids.push(CHANNEL_ID.fetch_add(1, Ordering::Relaxed));
         }
+        let seed = builder
+            .bootstrap_value
+            .into_data()
+            .map(|data| Arc::new(io::Data::new(data)));
         let output: Output<C, T, U, NO> = Output::builder(actor.client.clone())
             .bootstrap(builder.bootstrap)
+            .seed(seed)
+            .throttle(builder.throttle.map(|(sampling_frequency_hz, rate_hz)| {
+                (sampling_frequency_hz / NO as f64 / rate_hz)
+                    .round()
+                    .max(1f64) as usize
+            }))
+            .backpressure(builder.backpressure)
+            // the receivers are connected to the inputs in reverse order
+            .demultiplex(
+                builder
+                    .ranges
+                    .into_ranges()
+                    .map(|(ranges, slice)| (ranges.into_iter().rev().collect(), slice)),
+            )
             .senders(txs)
+            .ids(ids)
+            .label(builder.label)
+            .ordered(builder.ordered)
+            .channel(io::channel::<Tp, S<T, U>>)
             .build();
 
         if let Some(ref mut outputs) = actor.outputs {
@@ -301,6 +1124,7 @@ pub mod prelude {
     pub use super::{
         clients::{Logging, Sampler, Signal, Signals},
         model::Model,
-        Actor, AddOuput, ArcMutex, Initiator, IntoInputs, Task, Terminator,
+        Actor, AddOuput, ArcMutex, Initiator, IntoInputs, IntoOptionalInputs, IntoSampledInputs,
+        IntoSizedInputs, Task, Terminator,
     };
 }
@@ -257,3 +257,232 @@ macro_rules! impl_write {
         }
     };
 }
+#[macro_export]
+/**
+Wires a feedback loop between a plant and a controller
+
+`feedback_loop!(plant, controller, T, Sensor, Command)` sends the bootstrapped `Sensor` output of the `plant` to the `controller`
+and the `Command` output of the `controller` back to the `plant`, `T` is the data type of both channels.
+
+# Example
+The macro produces the same model as the manual wiring of the loop
+```
+use dos_actors::{feedback_loop, io::*, prelude::*, Update};
+use std::sync::Arc;
+#[derive(Default)]
+struct Client;
+impl Update for Client {}
+impl<U> Read<Vec<f64>, U> for Client {
+    fn read(&mut self, _data: Arc<Data<Vec<f64>, U>>) {}
+}
+impl<U> Write<Vec<f64>, U> for Client {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, U>>> {
+        Some(Arc::new(Data::new(vec![0f64])))
+    }
+}
+enum MountEncoders {}
+enum MountTorques {}
+
+let mut fem: Actor<_> = (Client, "FEM").into();
+let mut mount: Actor<_> = (Client, "Mount").into();
+fem.add_output()
+    .bootstrap()
+    .build::<Vec<f64>, MountEncoders>()
+    .into_input(&mut mount);
+mount
+    .add_output()
+    .build::<Vec<f64>, MountTorques>()
+    .into_input(&mut fem);
+let manual = Model::new(vec![Box::new(fem), Box::new(mount)]).topology();
+
+let mut fem: Actor<_> = (Client, "FEM").into();
+let mut mount: Actor<_> = (Client, "Mount").into();
+feedback_loop!(fem, mount, Vec<f64>, MountEncoders, MountTorques);
+let helper = Model::new(vec![Box::new(fem), Box::new(mount)]).topology();
+
+assert_eq!(manual, helper);
+```
+*/
+macro_rules! feedback_loop {
+    ($plant:ident, $controller:ident, $data:ty, $sensor:ty, $command:ty) => {
+        $crate::IntoInputs::into_input(
+            $crate::AddOuput::build::<$data, $sensor>($crate::AddOuput::bootstrap(
+                $plant.add_output(),
+            )),
+            &mut $controller,
+        );
+        $crate::IntoInputs::into_input(
+            $crate::AddOuput::build::<$data, $command>($controller.add_output()),
+            &mut $plant,
+        );
+    };
+}
+#[macro_export]
+/**
+Connects 2 actors running at different rates through a [Sampler](crate::clients::Sampler) actor
+
+`rate_transition!(producer => consumer: T, U)` connects the `U` output of the `producer` to the `consumer`
+through a new [Sampler](crate::clients::Sampler) actor that is returned, `T` is the data type of the channels
+(see [IntoSampledInputs](crate::IntoSampledInputs)).
+
+The rate transitions are inserted when the actors are connected, and not by the [Model](crate::model::Model),
+because the rates are part of the actor types: an output cannot be connected to an input of a different rate
+```compile_fail
+use dos_actors::prelude::*;
+enum Sig {};
+let mut source: Initiator<_> = Signals::new(1, 1000).into();
+let mut sink = Terminator::<_, 5>::new(Logging::<f64>::default().into_arcx());
+source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+```
+
+# Example
+A 1kHz source decimated to a 200Hz sink
+```
+# tokio_test::block_on(async {
+use dos_actors::{prelude::*, rate_transition};
+enum Sig {};
+let mut source: Initiator<_> = Signals::new(1, 1000).signals(Signal::Ramp { a: 1., b: 0. }).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_, 5>::new(logging.clone());
+let sampler = rate_transition!(source => sink: Vec<f64>, Sig);
+let plain = sampler.as_plain();
+assert_eq!((plain.inputs_rate, plain.outputs_rate), (1, 5));
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+    .check()?
+    .run()
+    .wait()
+    .await?;
+let logging = logging.lock().await;
+assert_eq!(logging.len(), 200);
+// the last sample of each 5 samples window
+assert_eq!(logging[..3], [4., 9., 14.]);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+macro_rules! rate_transition {
+    ($from:ident => $to:ident: $t:ty, $u:ty) => {
+        $crate::IntoSampledInputs::into_sampled_input(
+            $crate::AddOuput::build::<$t, $u>($from.add_output()),
+            &mut $to,
+        )
+    };
+}
+#[macro_export]
+/**
+Implements the [Read](crate::io::Read) and [Write](crate::io::Write) traits of a client for a list of `Vec<f64>` inputs and outputs
+
+`io_impls!(Client { read: [A => read_a], write: [B => write_b] })` implements `Read<Vec<f64>, A>` delegating to the client method
+`fn read_a(&mut self, data: &[f64])` and `Write<Vec<f64>, B>` delegating to the client method `fn write_b(&mut self) -> Option<Vec<f64>>`.
+
+# Example
+```
+use dos_actors::{io::*, io_impls, Update};
+use std::sync::Arc;
+enum A {}
+enum B {}
+enum C {}
+enum D {}
+#[derive(Default)]
+struct Client {
+    a: f64,
+    b: f64,
+}
+impl Client {
+    fn read_a(&mut self, data: &[f64]) {
+        self.a = data[0];
+    }
+    fn read_b(&mut self, data: &[f64]) {
+        self.b = data[0];
+    }
+    fn sum(&mut self) -> Option<Vec<f64>> {
+        Some(vec![self.a + self.b])
+    }
+    fn product(&mut self) -> Option<Vec<f64>> {
+        Some(vec![self.a * self.b])
+    }
+}
+impl Update for Client {}
+io_impls!(Client {
+    read: [A => read_a, B => read_b],
+    write: [C => sum, D => product]
+});
+
+let mut client = Client::default();
+<Client as Read<Vec<f64>, A>>::read(&mut client, Arc::new(Data::new(vec![2.])));
+<Client as Read<Vec<f64>, B>>::read(&mut client, Arc::new(Data::new(vec![3.])));
+let sum = <Client as Write<Vec<f64>, C>>::write(&mut client).unwrap();
+let product = <Client as Write<Vec<f64>, D>>::write(&mut client).unwrap();
+assert_eq!((sum[0], product[0]), (5., 6.));
+```
+*/
+macro_rules! io_impls {
+    ($client:ty {
+        read: [$($read:ty => $reader:ident),* $(,)?],
+        write: [$($write:ty => $writer:ident),* $(,)?] $(,)?
+    }) => {
+        $(
+            impl $crate::io::Read<Vec<f64>, $read> for $client {
+                fn read(&mut self, data: ::std::sync::Arc<$crate::io::Data<Vec<f64>, $read>>) {
+                    self.$reader(&data);
+                }
+            }
+        )*
+        $(
+            impl $crate::io::Write<Vec<f64>, $write> for $client {
+                fn write(&mut self) -> Option<::std::sync::Arc<$crate::io::Data<Vec<f64>, $write>>> {
+                    self.$writer()
+                        .map(|data| ::std::sync::Arc::new($crate::io::Data::new(data)))
+                }
+            }
+        )*
+    };
+}
+#[macro_export]
+/**
+Implements the [Read](crate::io::Read) traits of a [RelativeMotion](crate::clients::RelativeMotion) client
+
+`impl_relative_motion!(U, V)` implements `Read<Vec<f64>, U>` for the motion of the reference body
+and `Read<Vec<f64>, V>` for the motion of the moving body of `RelativeMotion<U, V>`.
+
+# Example
+```
+use dos_actors::{clients::RelativeMotion, impl_relative_motion, io::*};
+use std::sync::Arc;
+enum M1Rbm {}
+enum M2Rbm {}
+enum M2M1Rbm {}
+impl_relative_motion!(M1Rbm, M2Rbm);
+
+let mut relative = RelativeMotion::<M1Rbm, M2Rbm>::new();
+<RelativeMotion<M1Rbm, M2Rbm> as Read<Vec<f64>, M1Rbm>>::read(
+    &mut relative,
+    Arc::new(Data::new(vec![1., 2.])),
+);
+assert!(<RelativeMotion<M1Rbm, M2Rbm> as Write<Vec<f64>, M2M1Rbm>>::write(&mut relative).is_none());
+<RelativeMotion<M1Rbm, M2Rbm> as Read<Vec<f64>, M2Rbm>>::read(
+    &mut relative,
+    Arc::new(Data::new(vec![3., 5.])),
+);
+let motion = <RelativeMotion<M1Rbm, M2Rbm> as Write<Vec<f64>, M2M1Rbm>>::write(&mut relative).unwrap();
+assert_eq!(**motion, vec![2., 3.]);
+```
+*/
+macro_rules! impl_relative_motion {
+    ($reference:ty, $body:ty) => {
+        impl $crate::io::Read<Vec<f64>, $reference>
+            for $crate::clients::RelativeMotion<$reference, $body>
+        {
+            fn read(&mut self, data: ::std::sync::Arc<$crate::io::Data<Vec<f64>, $reference>>) {
+                self.set_reference(&data);
+            }
+        }
+        impl $crate::io::Read<Vec<f64>, $body>
+            for $crate::clients::RelativeMotion<$reference, $body>
+        {
+            fn read(&mut self, data: ::std::sync::Arc<$crate::io::Data<Vec<f64>, $body>>) {
+                self.set_body(&data);
+            }
+        }
+    };
+}
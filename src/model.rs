@@ -75,7 +75,7 @@ Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
        .check()?
        .run();
 # Ok::<(), dos_actors::model::ModelError>(())
-# });
+# }).unwrap();
 ```
 and wait for the tasks to finish:
 ```
@@ -94,7 +94,7 @@ Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
        .wait()
        .await?;
 # Ok::<(), dos_actors::model::ModelError>(())
-# });
+# }).unwrap();
 ```
 Once the model run to completion, the data from `logging` is read with:
 ```
@@ -114,7 +114,7 @@ Once the model run to completion, the data from `logging` is read with:
 #       .await?;
 let data: &[f64]  = &logging.lock().await;
 # Ok::<(), dos_actors::model::ModelError>(())
-# });
+# }).unwrap();
 ```
 
 [actor]: crate::actor
@@ -129,13 +129,26 @@ let data: &[f64]  = &logging.lock().await;
 */
 
 use crate::{
-    actor::{PlainActor, PlainOutput},
-    Task,
+    actor::{Gate, NonFinite, OutputKind, PlainActor, Progress, LOOP_TIME_BUCKETS},
+    AddOuput, Initiator, Task, Update,
 };
 use chrono::{DateTime, Local, SecondsFormat};
+use futures::future::join_all;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::BTreeMap, fs::File, io::Write, marker::PhantomData, path::Path, process::Command,
-    time::Instant,
+    any::Any,
+    collections::BTreeMap,
+    fs::File,
+    io::Write,
+    marker::PhantomData,
+    path::Path,
+    process::Command,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -146,10 +159,52 @@ pub enum ModelError {
     TaskError(#[from] tokio::task::JoinError),
     #[error("Actor IO inconsistency")]
     ActorIO(#[from] crate::ActorError),
+    #[error("{0} receives the throttled output {1} but it is not a terminator")]
+    Throttled(String, String),
+    #[error(
+        "{0} inputs rate ({1}) and outputs rate ({2}) must be integer multiples of each other"
+    )]
+    NonIntegerRateRatio(String, usize, usize),
+    #[error("model timed out after {timeout:?}, actors progress:\n{}", progress.iter().map(|p| p.to_string()).collect::<Vec<_>>().join("\n"))]
+    Timeout {
+        timeout: Duration,
+        progress: Vec<ActorProgress>,
+    },
+    #[error("model step #{0} failed")]
+    Step(usize, #[source] crate::ActorError),
+    #[error("{0} input {1} is not connected to any output of the model actors")]
+    OrphanInput(String, String),
+    #[error("{0} output {1} is not connected to any input of the model actors")]
+    OrphanOutput(String, String),
+    #[error("{0} client is not connected to any other actor")]
+    Unconnected(String),
+    #[error("model stalled: no client updated for {stall_timeout:?}, actors progress:\n{}", progress.iter().map(|p| p.to_string()).collect::<Vec<_>>().join("\n"))]
+    Stalled {
+        stall_timeout: Duration,
+        progress: Vec<ActorProgress>,
+    },
+    #[error("{actor} produced a non-finite value on output {output} at step {step}")]
+    NonFinite {
+        actor: String,
+        output: String,
+        step: usize,
+    },
+    #[error("the actors {} form a feedback loop without bootstrapped outputs", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("{0} bootstrapped output {1} is sent {2} times per step but a single sample is bootstrapped")]
+    MultiRateBootstrap(String, String, usize),
 }
 
 type Result<T> = std::result::Result<T, ModelError>;
 
+// Derives the seed of the actor #`i` from the master seed (SplitMix64 finalizer)
+fn sub_seed(seed: u64, i: u64) -> u64 {
+    let mut z = seed.wrapping_add((i + 1).wrapping_mul(0x9e3779b97f4a7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
 /// [Model] initial state
 pub enum Unknown {}
 /// Valid [Model] state
@@ -168,6 +223,94 @@ pub struct Model<State> {
     task_handles: Option<Vec<tokio::task::JoinHandle<()>>>,
     state: PhantomData<State>,
     start: Instant,
+    deterministic: bool,
+    steps: Vec<Arc<AtomicUsize>>,
+    summary: Option<Summary>,
+    timeout: Option<Duration>,
+    stall_timeout: Option<Duration>,
+    loop_times: bool,
+    progress: Vec<(String, Arc<Progress>)>,
+    gate: Arc<Gate>,
+    nan_guard: Vec<flume::Receiver<NonFinite>>,
+}
+
+/// Snapshot of an actor progress
+#[derive(Debug, Clone)]
+pub struct ActorProgress {
+    /// Actor name
+    pub name: String,
+    /// # of client updates
+    pub iterations: usize,
+    /// Time elapsed since the last client update
+    pub idle: Option<Duration>,
+}
+impl std::fmt::Display for ActorProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, " - {}: {} updates", self.name, self.iterations)?;
+        match self.idle {
+            Some(idle) => write!(f, ", last one {} ago", humantime::format_duration(idle)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Histogram of the time in-between the client updates of an actor
+#[derive(Debug, Clone)]
+pub struct LoopTimes {
+    /// Actor name
+    pub name: String,
+    /// # of loop times per bucket, see [LOOP_TIME_BUCKETS](crate::actor::LOOP_TIME_BUCKETS)
+    pub counts: Vec<usize>,
+}
+impl LoopTimes {
+    /// Returns the # of non-empty buckets
+    pub fn spread(&self) -> usize {
+        self.counts.iter().filter(|&&count| count > 0).count()
+    }
+}
+impl std::fmt::Display for LoopTimes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, " - {}:", self.name)?;
+        for (k, count) in self.counts.iter().enumerate() {
+            let bucket = match (
+                k.checked_sub(1).map(|k| LOOP_TIME_BUCKETS[k]),
+                LOOP_TIME_BUCKETS.get(k),
+            ) {
+                (None, Some(upper)) => format!("< {}", humantime::format_duration(*upper)),
+                (Some(lower), Some(upper)) => format!(
+                    "{} - {}",
+                    humantime::format_duration(lower),
+                    humantime::format_duration(*upper)
+                ),
+                (Some(lower), None) => format!(">= {}", humantime::format_duration(lower)),
+                (None, None) => unreachable!(),
+            };
+            writeln!(f, "   {:>14}: {}", bucket, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// [Model] run summary
+#[derive(Debug, Clone)]
+pub struct Summary {
+    /// Wall-clock time from launch to completion
+    pub elapsed: Duration,
+    /// # of simulation steps, i.e. the largest # of iterations of the [Initiator](crate::Initiator)s
+    pub n_step: usize,
+    /// Simulation steps per second
+    pub steps_per_second: f64,
+}
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} steps in {} ({:.0} steps/s)",
+            self.n_step,
+            humantime::format_duration(self.elapsed),
+            self.steps_per_second
+        )
+    }
 }
 
 #[doc(hidden)]
@@ -184,14 +327,147 @@ where
             .as_ref()
             .map(|actors| Graph::new(actors.iter().map(|a| a.as_plain()).collect()))
     }
+    /// Returns the [Topology] of the model
+    pub fn topology(&self) -> Option<Topology> {
+        self.actors
+            .as_ref()
+            .map(|actors| Topology::new(actors.iter().map(|a| a.as_plain()).collect()))
+    }
+    /**
+    Returns the connections between the model actors
+
+    Each [Connection] is an output of an actor with all the actors receiving the output,
+    the connections are listed in the order the actors are in the model and the outputs are added to the actors.
+
+    # Example
+    The connections of a mount-like model: a source sending the encoders to the controller that sends the torques to a logger
+    ```
+    use dos_actors::{clients::Integrator, prelude::*};
+    enum MountEncoders {};
+    enum MountTorques {};
+    let mut source: Initiator<_> = (Signals::new(14, 1000), "source").into();
+    let mut mount: Actor<_> = (Integrator::<f64, MountEncoders>::new(14), "mount").into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .build::<Vec<f64>, MountEncoders>()
+        .into_input(&mut mount);
+    mount
+        .add_output()
+        .bootstrap()
+        .unbounded()
+        .label("torques")
+        .build::<Vec<f64>, MountTorques>()
+        .into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(mount), Box::new(sink)]);
+    let connections = model.connections();
+    assert_eq!(connections.len(), 2);
+    let encoders = &connections[0];
+    assert_eq!(
+        (encoders.producer.as_str(), encoders.consumers.as_slice()),
+        ("source", ["mount".to_string()].as_slice())
+    );
+    assert!(encoders.data.ends_with("MountEncoders"));
+    assert_eq!(encoders.label, None);
+    assert_eq!((encoders.rate, encoders.bootstrap, encoders.capacity), (1, false, 1));
+    let torques = &connections[1];
+    assert_eq!(torques.producer, "mount");
+    assert_eq!(torques.consumers.len(), 1);
+    assert!(torques.consumers[0].contains("Logging"));
+    assert!(torques.data.ends_with("MountTorques"));
+    assert_eq!(torques.label.as_deref(), Some("torques"));
+    assert_eq!((torques.rate, torques.bootstrap, torques.capacity), (1, true, usize::MAX));
+    ```
+    */
+    pub fn connections(&self) -> Vec<Connection> {
+        self.actors
+            .as_ref()
+            .map(|actors| connections(&actors.iter().map(|a| a.as_plain()).collect::<Vec<_>>()))
+            .unwrap_or_default()
+    }
+    /**
+    Returns the [Topology] of the model in the JSON format
+
+    Requires the `serde` feature
+
+    # Example
+    ```
+    use dos_actors::{model::Topology, prelude::*};
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut sampler: Actor<_, 1, 10> = Sampler::<Vec<f64>, Sig>::default().into();
+    let mut sink = Terminator::<_, 10>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+    sampler.add_output().bootstrap().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)]);
+    let topology: Topology = serde_json::from_str(&model.topology_json())?;
+    assert_eq!(topology, model.topology().unwrap());
+    assert_eq!(topology.actors.len(), 3);
+    assert_eq!(
+        topology
+            .actors
+            .iter()
+            .map(|actor| (actor.inputs_rate, actor.outputs_rate))
+            .collect::<Vec<_>>(),
+        vec![(0, 1), (1, 10), (10, 0)]
+    );
+    assert_eq!(topology.edges.len(), 2);
+    assert!(topology.edges[0].producer.contains("Signals"));
+    assert!(topology.edges[0].consumer.contains("Sampler"));
+    assert_eq!((topology.edges[0].rate, topology.edges[0].bootstrap), (1, false));
+    assert!(topology.edges[1].consumer.contains("Logging"));
+    assert_eq!((topology.edges[1].rate, topology.edges[1].bootstrap), (10, true));
+    # Ok::<(), serde_json::Error>(())
+    ```
+    */
+    #[cfg(feature = "serde")]
+    pub fn topology_json(&self) -> String {
+        self.topology()
+            .map(|topology| {
+                serde_json::to_string_pretty(&topology)
+                    .expect("failed to serialize the model topology")
+            })
+            .unwrap_or_default()
+    }
     /// Produces the model flowchart
     pub fn flowchart(self) -> Self {
+        let graph = self.graph();
+        self.write_flowchart(graph)
+    }
+    /**
+    Produces the model flowchart with the actors rates
+
+    The actors are annotated with their inputs and outputs rates `(NI, NO)`,
+    see [Graph::rates]
+
+    # Example
+    ```
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut sampler: Actor<_, 1, 10> = Sampler::<Vec<f64>, Sig>::default().into();
+    let mut sink = Terminator::<_, 10>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+    sampler.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)]);
+    let dot = model.graph().unwrap().rates().to_string();
+    assert!(dot.contains(r#"Signals [label="Signals\n(0, 1)"]"#));
+    assert!(dot.contains(r#"Sampler [label="Sampler\n(1, 10)"]"#));
+    assert!(dot.contains(r#"Logging [label="Logging\n(10, 0)"]"#));
+    assert!(dot.contains(r#"style="dotted""#));
+    ```
+    */
+    pub fn visualize_rates(self) -> Self {
+        let graph = self.graph().map(|graph| graph.rates());
+        self.write_flowchart(graph)
+    }
+    fn write_flowchart(self, graph: Option<Graph>) -> Self {
         let name = self
             .name
             .clone()
             .unwrap_or_else(|| "integrated_model".to_string());
         let path = Path::new(&name);
-        if let Some(graph) = self.graph() {
+        if let Some(graph) = graph {
             graph
                 .to_dot(path.with_extension("dot"))
                 .expect("Failed to write Graphviz dot file");
@@ -216,6 +492,524 @@ impl Model<Unknown> {
             task_handles: None,
             state: PhantomData,
             start: Instant::now(),
+            deterministic: false,
+            steps: Vec::new(),
+            summary: None,
+            timeout: None,
+            stall_timeout: None,
+            loop_times: false,
+            progress: Vec::new(),
+            gate: Default::default(),
+            nan_guard: Vec::new(),
+        }
+    }
+    /**
+    Sizes the bounded channels according to the actors rates
+
+    The capacity of the channels of an actor outputs is set to the number of samples the actor sends
+    in a single iteration of its loop i.e. `NI/NO` for an upsampling actor `Actor<_, NI, NO>`.
+    The capacity of a channel is never reduced and unbounded channels are left untouched.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_, 10> = Signals::new(1, 10).into();
+    let mut upsampler: Actor<_, 10, 1> = Sampler::<Vec<f64>, Sig>::default().into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut upsampler);
+    upsampler.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(upsampler), Box::new(sink)])
+        .auto_capacity();
+    let topology = model.topology().unwrap();
+    let edge = topology.edges.iter().find(|edge| edge.rate == 1).unwrap();
+    assert!(edge.capacity >= 10);
+    model.check()?.run().wait().await?;
+    assert_eq!(logging.lock().await.len(), 100);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn auto_capacity(mut self) -> Self {
+        if let Some(actors) = self.actors.as_mut() {
+            let receivers: Vec<_> = actors
+                .iter_mut()
+                .flat_map(|actor| actor.resize_outputs(None))
+                .collect();
+            for (id, rx) in receivers {
+                actors
+                    .iter_mut()
+                    .try_fold(rx, |rx, actor| actor.rewire_input(id, rx));
+            }
+        }
+        self
+    }
+    /**
+    Aborts the model if it has not completed within `timeout`
+
+    [Model::wait] then returns a [ModelError::Timeout] error with the progress of each actor.
+    The actors of a [deterministic](Model::deterministic) model cannot be aborted.
+
+    # Example
+    A sink waiting for its client that is held somewhere else
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{model::ModelError, prelude::*};
+    use std::time::Duration;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let held = logging.lock().await;
+    let result = Model::new(vec![Box::new(source), Box::new(sink)])
+        .timeout(Duration::from_millis(500))
+        .check()?
+        .run()
+        .wait()
+        .await;
+    drop(held);
+    match result {
+        Err(ModelError::Timeout { progress, .. }) => {
+            let iterations = |client: &str| {
+                progress
+                    .iter()
+                    .find(|p| p.name.contains(client))
+                    .unwrap()
+                    .iterations
+            };
+            assert!(iterations("Signals") > 0);
+            assert_eq!(iterations("Logging"), 0);
+        }
+        _ => panic!("expected a timeout"),
+    }
+    # Ok::<(), ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+    /**
+    Aborts the model if none of the actors clients has been updated for `stall_timeout`
+
+    Unlike [Model::timeout], a long simulation is not aborted as long as it makes progress.
+    [Model::wait] returns a [ModelError::Stalled] error with the progress of each actor.
+    The actors of a [deterministic](Model::deterministic) model cannot be aborted.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{model::ModelError, prelude::*};
+    use std::time::Duration;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let held = logging.lock().await;
+    let result = Model::new(vec![Box::new(source), Box::new(sink)])
+        .stall_timeout(Duration::from_millis(100))
+        .check()?
+        .run()
+        .wait()
+        .await;
+    drop(held);
+    match result {
+        Err(ModelError::Stalled { progress, .. }) => {
+            let sink = progress.iter().find(|p| p.name.contains("Logging")).unwrap();
+            assert_eq!(sink.iterations, 0);
+        }
+        _ => panic!("expected a stall"),
+    }
+    # Ok::<(), ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn stall_timeout(self, stall_timeout: Duration) -> Self {
+        Self {
+            stall_timeout: Some(stall_timeout),
+            ..self
+        }
+    }
+    /**
+    Shares a simulation clock sampled at `sampling_frequency_hz` with all the actors
+
+    The clients receive the [SimClock](crate::SimClock) with [Update::tick](crate::Update::tick) before each update.
+
+    # Example
+    Two clients, updated at different rates, report identical times at the same step
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{io::*, prelude::*, SimClock, Update};
+    use std::sync::Arc;
+    #[derive(Default)]
+    struct Clock(Vec<(usize, f64)>);
+    impl Update for Clock {
+        fn tick(&mut self, clock: &SimClock) {
+            self.0.push((clock.step(), clock.time()));
+        }
+    }
+    impl<U> Read<Vec<f64>, U> for Clock {
+        fn read(&mut self, _data: Arc<Data<Vec<f64>, U>>) {}
+    }
+    enum A {};
+    enum B {};
+    let mut a: Initiator<_> = Signals::new(1, 10).into();
+    let mut b: Initiator<_, 2> = Signals::new(1, 5).into();
+    let clock_a = Clock::default().into_arcx();
+    let clock_b = Clock::default().into_arcx();
+    let mut sink_a = Terminator::<_>::new(clock_a.clone());
+    let mut sink_b = Terminator::<_, 2>::new(clock_b.clone());
+    a.add_output().build::<Vec<f64>, A>().into_input(&mut sink_a);
+    b.add_output().build::<Vec<f64>, B>().into_input(&mut sink_b);
+    Model::new(vec![Box::new(a), Box::new(b), Box::new(sink_a), Box::new(sink_b)])
+        .clock(1e3)
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let (clock_a, clock_b) = (clock_a.lock().await, clock_b.lock().await);
+    assert_eq!(clock_a.0.len(), 10);
+    assert_eq!(clock_b.0.len(), 5);
+    for (step, time) in &clock_b.0 {
+        assert_eq!(clock_a.0.iter().find(|(s, _)| s == step).unwrap().1, *time);
+    }
+    assert_eq!(clock_b.0[4], (8, 8e-3));
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn clock(mut self, sampling_frequency_hz: f64) -> Self {
+        self.actors
+            .iter_mut()
+            .flatten()
+            .for_each(|actor| actor.clock(sampling_frequency_hz));
+        self
+    }
+    /**
+    Extracts the actors named with one of the `tags`
+
+    The other actors are removed from the model.
+    The outputs of the extracted actors that were sent to removed actors are discarded
+    (the loggers of these outputs must be part of the subgraph to record them)
+    and the inputs received from removed actors are left dangling until they are fed with [Model::replay].
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{clients::Sink, model::Unknown, prelude::*};
+    use std::sync::{Arc, Mutex};
+    enum Plant {};
+    enum Command {};
+    type Recorder = Arc<tokio::sync::Mutex<Logging<f64>>>;
+    type Commands = Arc<Mutex<Vec<f64>>>;
+    fn model() -> (Model<Unknown>, Recorder, Commands) {
+        let mut plant: Initiator<_> = (
+            Signals::new(1, 10).signals(Signal::Ramp { a: 1., b: 0. }),
+            "plant",
+        )
+            .into();
+        let mut controller: Actor<_> =
+            (Sampler::<Vec<f64>, Plant, Command>::default(), "controller").into();
+        let recorder = Logging::<f64>::default().into_arcx();
+        let mut recorder_sink = Terminator::<_>::new(recorder.clone());
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let captured = commands.clone();
+        let mut logger_sink: Terminator<_> = (
+            Sink::<Command>::new(move |_, data: &Vec<f64>| captured.lock().unwrap().push(data[0])),
+            "logger",
+        )
+            .into();
+        plant
+            .add_output()
+            .multiplex(2)
+            .build::<Vec<f64>, Plant>()
+            .into_input(&mut controller)
+            .into_input(&mut recorder_sink);
+        controller.add_output().build::<Vec<f64>, Command>().into_input(&mut logger_sink);
+        let actors: Vec<Box<dyn Task>> = vec![
+            Box::new(plant),
+            Box::new(controller),
+            Box::new(recorder_sink),
+            Box::new(logger_sink),
+        ];
+        (Model::new(actors), recorder, commands)
+    }
+    // integrated model
+    let (integrated, recorder, commands) = model();
+    integrated.check()?.run().wait().await?;
+    // controller in isolation, fed with the recorded plant data
+    let (subgraph, _, replayed_commands) = model();
+    let replay: Initiator<_> = Signals::from_logging(&*recorder.lock().await, 0).into();
+    let subgraph = subgraph
+        .subgraph(&["controller", "logger"])
+        .replay::<_, Vec<f64>, Plant, 1>(replay);
+    assert_eq!(subgraph.topology().unwrap().actors.len(), 3);
+    subgraph.check()?.run().wait().await?;
+    let replayed_commands = replayed_commands.lock().unwrap();
+    assert_eq!(replayed_commands.len(), 10);
+    assert_eq!(*commands.lock().unwrap(), *replayed_commands);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn subgraph(mut self, tags: &[&str]) -> Self {
+        if let Some(actors) = self.actors.take() {
+            let mut actors: Actors = actors
+                .into_iter()
+                .filter(|actor| tags.contains(&actor.as_plain().client.as_str()))
+                .collect();
+            actors.iter_mut().for_each(|actor| actor.prune_outputs());
+            self.actors = Some(actors);
+        }
+        self
+    }
+    /**
+    Feeds a dangling input with the data of the initiator `actor`
+
+    The output `U` of `actor` is connected to the first input of type `U` of the model that has lost its sender,
+    see [Model::subgraph].
+    The initiator is discarded if there is no such input.
+    */
+    pub fn replay<C, T, U, const NO: usize>(mut self, mut actor: Initiator<C, NO>) -> Self
+    where
+        C: 'static + Update + Send + crate::io::Write<T, U>,
+        T: 'static + Send + Sync,
+        U: 'static + Send + Sync,
+    {
+        let (producer, mut rxs) = actor.add_output().build::<T, U>();
+        let id = producer.channel_id(0);
+        if let (Some(rx), Some(actors)) = (rxs.pop(), self.actors.as_mut()) {
+            let rx: crate::io::Receiver<Arc<crate::io::Data<T, U>>> = Box::new(rx);
+            let rx: Box<dyn Any + Send> = Box::new(rx);
+            match actors
+                .iter_mut()
+                .try_fold(rx, |rx, actor| actor.rewire_dangling_input(id, rx))
+            {
+                Some(_) => log::warn!(
+                    "no dangling input found for {}, the replay is discarded",
+                    std::any::type_name::<U>()
+                ),
+                None => actors.push(Box::new(actor)),
+            }
+        }
+        self
+    }
+    /**
+    Merges `other` into the model
+
+    The actors of both models are gathered into a single model, keeping the name of `self` if it has one.
+    The model is deterministic if either model is and the shortest of both timeouts is kept.
+    Connections between actors of both models are made before the actors are moved into their respective models.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Mechanics {};
+    enum Optics {};
+    let mut mechanics: Initiator<_> = Signals::new(1, 10).into();
+    let mechanics_logging = Logging::<f64>::default().into_arcx();
+    let mut mechanics_sink = Terminator::<_>::new(mechanics_logging.clone());
+    let mut optics: Initiator<_> = Signals::new(1, 10).into();
+    let optics_logging = Logging::<f64>::default().n_entry(2).into_arcx();
+    let mut optics_sink = Terminator::<_>::new(optics_logging.clone());
+    mechanics
+        .add_output()
+        .multiplex(2)
+        .build::<Vec<f64>, Mechanics>()
+        .into_input(&mut mechanics_sink)
+        // cross link between both models
+        .into_input(&mut optics_sink);
+    optics.add_output().build::<Vec<f64>, Optics>().into_input(&mut optics_sink);
+    let mechanical_model = Model::new(vec![Box::new(mechanics), Box::new(mechanics_sink)]);
+    let optical_model = Model::new(vec![Box::new(optics), Box::new(optics_sink)]);
+    let model = mechanical_model.merge(optical_model);
+    assert_eq!(model.topology().unwrap().actors.len(), 4);
+    model.check()?.run().wait().await?;
+    assert_eq!(mechanics_logging.lock().await.len(), 10);
+    assert_eq!(optics_logging.lock().await.len(), 10);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn merge(self, other: Model<Unknown>) -> Self {
+        let actors = match (self.actors, other.actors) {
+            (Some(mut actors), Some(other_actors)) => {
+                actors.extend(other_actors);
+                Some(actors)
+            }
+            (actors, other_actors) => actors.or(other_actors),
+        };
+        Self {
+            name: self.name.or(other.name),
+            actors,
+            task_handles: None,
+            state: PhantomData,
+            start: Instant::now(),
+            deterministic: self.deterministic || other.deterministic,
+            steps: Vec::new(),
+            summary: None,
+            timeout: match (self.timeout, other.timeout) {
+                (Some(timeout), Some(other_timeout)) => Some(timeout.min(other_timeout)),
+                (timeout, other_timeout) => timeout.or(other_timeout),
+            },
+            stall_timeout: match (self.stall_timeout, other.stall_timeout) {
+                (Some(timeout), Some(other_timeout)) => Some(timeout.min(other_timeout)),
+                (timeout, other_timeout) => timeout.or(other_timeout),
+            },
+            loop_times: self.loop_times || other.loop_times,
+            progress: Vec::new(),
+            gate: Default::default(),
+            nan_guard: self.nan_guard.into_iter().chain(other.nan_guard).collect(),
+        }
+    }
+    /**
+    Seeds the random number generators of all the clients from the master `seed`
+
+    Each actor client receives, with [Update::seed], a distinct seed derived from `seed` and from the index of the actor in the model,
+    so the same master seed and the same ordering of the actors reproduce the same stochastic run.
+    */
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.actors
+            .iter_mut()
+            .flatten()
+            .enumerate()
+            .for_each(|(i, actor)| actor.seed(sub_seed(seed, i as u64)));
+        self
+    }
+    /**
+    Checks the `Vec<f64>` outputs of all the actors for NaN or infinite values
+
+    The first non-finite value ends the loop of the actor that produced it, without being sent,
+    and the model [wait](Model::wait) returns [ModelError::NonFinite] with the name of the actor, the output and the client update step.
+    The NaN guard must be set after all the actors have been added to the model.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{clients::Integrator, model::ModelError, prelude::*};
+    enum U {};
+    enum Y {};
+    let mut samples = vec![1f64; 100];
+    samples[42] = f64::NAN;
+    let mut source: Initiator<_> = (Signals::new(1, 100).signals(Signal::Sequence(samples)), "source").into();
+    let mut integrator: Actor<_> = (Integrator::<f64, U>::new(1), "integrator").into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, U>().into_input(&mut integrator);
+    integrator.add_output().build::<Vec<f64>, Y>().into_input(&mut sink);
+    let result = Model::new(vec![Box::new(source), Box::new(integrator), Box::new(sink)])
+        .nan_guard()
+        .check()?
+        .run()
+        .wait()
+        .await;
+    match result {
+        Err(ModelError::NonFinite { actor, step, .. }) => {
+            assert_eq!(actor, "source");
+            assert_eq!(step, 42);
+        }
+        _ => panic!("the NaN is not caught"),
+    }
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+
+    The NaN guards of [merge](Model::merge)d models are kept
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{model::ModelError, prelude::*};
+    enum U {};
+    let mut samples = vec![1f64; 10];
+    samples[7] = f64::INFINITY;
+    let mut first_source: Initiator<_> = Signals::new(1, 10).into();
+    let mut second_source: Initiator<_> =
+        (Signals::new(1, 10).signals(Signal::Sequence(samples)), "second").into();
+    let mut first_sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    let mut second_sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    first_source.add_output().build::<Vec<f64>, U>().into_input(&mut first_sink);
+    second_source.add_output().build::<Vec<f64>, U>().into_input(&mut second_sink);
+    let result = Model::new(vec![Box::new(first_source), Box::new(first_sink)])
+        .nan_guard()
+        .merge(Model::new(vec![Box::new(second_source), Box::new(second_sink)]).nan_guard())
+        .check()?
+        .run()
+        .wait()
+        .await;
+    match result {
+        Err(ModelError::NonFinite { actor, step, .. }) => {
+            assert_eq!(actor, "second");
+            assert_eq!(step, 7);
+        }
+        _ => panic!("the infinite value is not caught"),
+    }
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn nan_guard(mut self) -> Self {
+        let (tx, rx) = flume::unbounded();
+        self.actors
+            .iter_mut()
+            .flatten()
+            .for_each(|actor| actor.nan_guard(tx.clone()));
+        self.nan_guard.push(rx);
+        self
+    }
+    /**
+    Records the histogram of the loop times of each actor
+
+    The loop time is the time in-between two consecutive client updates, it is sorted into coarse buckets
+    (see [LOOP_TIME_BUCKETS](crate::actor::LOOP_TIME_BUCKETS)) in order to reveal the jitter of the actors loop.
+    The histograms are printed once the model has completed and are returned by [Model::loop_times].
+    The recording starts when the model is [run](Model::run), including the actors of the models [merge](Model::merge)d afterwards.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{prelude::*, Update};
+    use std::{sync::Arc, time::Duration};
+    struct Jittery(usize);
+    impl Update for Jittery {
+        fn update(&mut self) {
+            self.0 += 1;
+            let delay = if self.0 % 2 == 0 { 2 } else { 20 };
+            std::thread::sleep(Duration::from_millis(delay));
+        }
+    }
+    impl<U> dos_actors::io::Read<Vec<f64>, U> for Jittery {
+        fn read(&mut self, _data: Arc<dos_actors::io::Data<Vec<f64>, U>>) {}
+    }
+    enum Sig {};
+    let mut source: Initiator<_> = (Signals::new(1, 20), "source").into();
+    let mut sink: Terminator<_> = (Jittery(0), "jittery").into();
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source)])
+        .histogram_of_loop_times()
+        .merge(Model::new(vec![Box::new(sink)]))
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let loop_times = model.loop_times();
+    let jittery = loop_times.iter().find(|h| h.name == "jittery").unwrap();
+    // 19 loop times alternating between ~2ms and ~20ms
+    assert_eq!(jittery.counts.iter().sum::<usize>(), 19);
+    assert!(jittery.spread() >= 2);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn histogram_of_loop_times(self) -> Self {
+        Self {
+            loop_times: true,
+            ..self
         }
     }
     /// Sets the model name
@@ -225,7 +1019,268 @@ impl Model<Unknown> {
             ..self
         }
     }
-    /// Validates actors inputs and outputs
+    /**
+    Runs the model in deterministic mode
+
+    All the actors are run within a single thread and are [step](Stepper::step)ped in a fixed order:
+    each actor runs the iterations of a step after the actors it receives data from, bootstrapped outputs excepted,
+    and its inputs are read in the order they were added to the actor.
+    The throughput of the model is reduced but the execution of the actors is reproducible from one run to the next.
+
+    [Model::check] returns a [ModelError::MultiRateBootstrap] error if a bootstrapped output is sent more than once per step.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum A {};
+    enum B {};
+    async fn run() -> Vec<u8> {
+        let mut a: Initiator<_> = Signals::new(1, 100).signals(Signal::Ramp { a: 1., b: 0. }).into();
+        let mut b: Initiator<_> = Signals::new(2, 100).signals(Signal::Constant(1.)).into();
+        let logging = Logging::<f64>::default().n_entry(2).into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        a.add_output().build::<Vec<f64>, A>().into_input(&mut sink);
+        b.add_output().build::<Vec<f64>, B>().into_input(&mut sink);
+        Model::new(vec![Box::new(sink), Box::new(a), Box::new(b)])
+            .deterministic()
+            .check()
+            .unwrap()
+            .run()
+            .wait()
+            .await
+            .unwrap();
+        let bytes = logging.lock().await.iter().flat_map(|x| x.to_le_bytes()).collect();
+        bytes
+    }
+    assert_eq!(run().await, run().await);
+    # });
+    ```
+
+    Two sinks sharing the same logger record the samples of a fast and of a slow source
+    in the same order, whatever the sources speed
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{io::{Data, Write}, prelude::*, Update};
+    use std::{sync::Arc, time::Duration};
+    enum A {};
+    enum B {};
+    // A source slowed down by a delay at each update
+    struct Slow(usize);
+    impl Update for Slow {
+        fn update(&mut self) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+    impl Write<Vec<f64>, B> for Slow {
+        fn write(&mut self) -> Option<Arc<Data<Vec<f64>, B>>> {
+            self.0.checked_sub(1).map(|n| {
+                self.0 = n;
+                Arc::new(Data::new(vec![-1.]))
+            })
+        }
+    }
+    let n = 10;
+    let mut fast: Initiator<_> = Signals::new(1, n).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let mut slow: Initiator<_> = Slow(n).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut fast_sink = Terminator::<_>::new(logging.clone());
+    let mut slow_sink = Terminator::<_>::new(logging.clone());
+    fast.add_output().build::<Vec<f64>, A>().into_input(&mut fast_sink);
+    slow.add_output().build::<Vec<f64>, B>().into_input(&mut slow_sink);
+    Model::new(vec![
+        Box::new(slow),
+        Box::new(slow_sink),
+        Box::new(fast),
+        Box::new(fast_sink),
+    ])
+    .deterministic()
+    .check()?
+    .run()
+    .wait()
+    .await?;
+    let samples: Vec<f64> = (0..n).flat_map(|i| [-1., i as f64]).collect();
+    assert_eq!(*logging.lock().await.as_slice(), samples);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+
+    A deterministic model with an [optional input](crate::IntoOptionalInputs)
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{io::{Data, ReadOption}, prelude::*, Update};
+    use std::{sync::Arc, time::Duration};
+    enum Sig {};
+    #[derive(Default)]
+    struct Watcher(Vec<Option<f64>>);
+    impl Update for Watcher {}
+    impl ReadOption<Vec<f64>, Sig> for Watcher {
+        fn read_option(&mut self, data: Option<Arc<Data<Vec<f64>, Sig>>>) {
+            self.0.push(data.map(|data| data[0]));
+        }
+    }
+    let mut source: Initiator<_> = Signals::new(1, 5).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let watcher = Watcher::default().into_arcx();
+    let mut sink = Terminator::<_>::new(watcher.clone());
+    source
+        .add_output()
+        .build::<Vec<f64>, Sig>()
+        .into_optional_input(&mut sink, Duration::from_secs(1));
+    Model::new(vec![Box::new(source), Box::new(sink)])
+        .deterministic()
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let samples: Vec<Option<f64>> = (0..5).map(|i| Some(i as f64)).collect();
+    assert_eq!(watcher.lock().await.0[..5], samples);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn deterministic(self) -> Self {
+        Self {
+            deterministic: true,
+            ..self
+        }
+    }
+    /**
+    Validates actors inputs and outputs
+
+    The inputs and outputs rates of an actor must be integer multiples of each other,
+    unless the actor is [fractional](crate::Actor::fractional):
+    ```
+    use dos_actors::{model::ModelError, prelude::*};
+    enum Sig {};
+    let mut source: Initiator<_, 2> = Signals::new(1, 100).into();
+    let mut sampler: Actor<_, 2, 3> = Sampler::<Vec<f64>, Sig>::default().into();
+    let mut sink = Terminator::<_, 3>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sampler);
+    sampler.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let check = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)]).check();
+    assert!(matches!(check, Err(ModelError::NonIntegerRateRatio(_, 2, 3))));
+    ```
+
+    Each input must be connected to the output of one of the model actors:
+    ```
+    use dos_actors::{model::ModelError, prelude::*};
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let check = Model::new(vec![Box::new(sink)]).check();
+    assert!(matches!(check, Err(ModelError::OrphanInput(_, _))));
+    ```
+    and each output must be connected to the input of one of the model actors:
+    ```
+    use dos_actors::{model::ModelError, prelude::*};
+    enum Sig {};
+    enum Unused {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    source.add_output().build::<Vec<f64>, Unused>();
+    let check = Model::new(vec![Box::new(source), Box::new(sink)]).check();
+    match check {
+        Err(ModelError::OrphanOutput(_, output)) => assert!(output.ends_with("Unused")),
+        _ => panic!("the orphan output is not detected"),
+    }
+    ```
+
+    An actor without inputs and outputs never calls its client:
+    ```
+    use dos_actors::{model::ModelError, prelude::*};
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    let idle: Actor<_, 0, 0> = (Logging::<f64>::default(), "idle").into();
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let check = Model::new(vec![Box::new(source), Box::new(sink), Box::new(idle)]).check();
+    assert!(matches!(check, Err(ModelError::Unconnected(actor)) if actor == "idle"));
+    ```
+
+    A throttled output can only be received by a [Terminator](crate::Terminator),
+    another output of the same data is not restricted:
+    ```
+    use dos_actors::{clients::Integrator, model::ModelError, prelude::*};
+    enum U {};
+    enum Y {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut integrator: Actor<_> = Integrator::<f64, U>::new(1).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().n_entry(2).into_arcx());
+    source
+        .add_output()
+        .throttle_hz(1000., 100.)
+        .build::<Vec<f64>, U>()
+        .into_input(&mut sink);
+    source.add_output().build::<Vec<f64>, U>().into_input(&mut integrator);
+    integrator.add_output().build::<Vec<f64>, Y>().into_input(&mut sink);
+    let check = Model::new(vec![Box::new(source), Box::new(integrator), Box::new(sink)]).check();
+    assert!(check.is_ok());
+    ```
+    and it cannot be bootstrapped:
+    ```
+    use dos_actors::{model::ModelError, prelude::*, ActorError};
+    enum U {};
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .bootstrap()
+        .throttle_hz(1000., 100.)
+        .build::<Vec<f64>, U>()
+        .into_input(&mut sink);
+    let check = Model::new(vec![Box::new(source), Box::new(sink)]).check();
+    assert!(matches!(
+        check,
+        Err(ModelError::ActorIO(ActorError::ThrottledBootstrap(_)))
+    ));
+    ```
+
+    A feedback loop must have at least one bootstrapped output, otherwise the actors of the loop wait for each other forever:
+    ```
+    use dos_actors::{clients::Integrator, model::ModelError, prelude::*};
+    enum U {};
+    enum Y {};
+    let mut a: Actor<_> = (Integrator::<f64, Y>::new(1), "A").into();
+    let mut b: Actor<_> = (Integrator::<f64, U>::new(1), "B").into();
+    a.add_output().build::<Vec<f64>, U>().into_input(&mut b);
+    b.add_output().build::<Vec<f64>, Y>().into_input(&mut a);
+    let check = Model::new(vec![Box::new(a), Box::new(b)]).check();
+    match check {
+        Err(ModelError::Cycle(actors)) => assert_eq!(actors, vec!["A", "B"]),
+        _ => panic!("the feedback loop is not detected"),
+    }
+    ```
+    A loop closed by an [optional input](crate::IntoOptionalInputs) does not wait for the data and does not need to be bootstrapped:
+    ```
+    use dos_actors::{clients::Integrator, io, prelude::*, Update};
+    use std::{sync::Arc, time::Duration};
+    struct Hold(Vec<f64>);
+    impl Update for Hold {}
+    impl<U> io::ReadOption<Vec<f64>, U> for Hold {
+        fn read_option(&mut self, data: Option<Arc<io::Data<Vec<f64>, U>>>) {
+            if let Some(data) = data {
+                self.0 = (**data).clone();
+            }
+        }
+    }
+    impl<U> io::Write<Vec<f64>, U> for Hold {
+        fn write(&mut self) -> Option<Arc<io::Data<Vec<f64>, U>>> {
+            Some(Arc::new(io::Data::new(self.0.clone())))
+        }
+    }
+    enum U {};
+    enum Y {};
+    let mut a: Actor<_> = (Integrator::<f64, Y>::new(1), "A").into();
+    let mut b: Actor<_> = (Hold(vec![0.]), "B").into();
+    a.add_output()
+        .build::<Vec<f64>, U>()
+        .into_optional_input(&mut b, Duration::from_millis(10));
+    b.add_output().build::<Vec<f64>, Y>().into_input(&mut a);
+    assert!(Model::new(vec![Box::new(a), Box::new(b)]).check().is_ok());
+    ```
+    */
     pub fn check(self) -> Result<Model<Ready>> {
         match self.actors {
             Some(ref actors) => {
@@ -233,12 +1288,107 @@ impl Model<Unknown> {
                     actor.check_inputs()?;
                     actor.check_outputs()?;
                 }
+                let plain_actors: Vec<_> = actors.iter().map(|a| a.as_plain()).collect();
+                if let Some(actor) = plain_actors.iter().find(|actor| {
+                    let (ni, no) = (actor.inputs_rate, actor.outputs_rate);
+                    !actor.fractional_rates && ni > 0 && no > 0 && ni.max(no) % ni.min(no) != 0
+                }) {
+                    return Err(ModelError::NonIntegerRateRatio(
+                        actor.client.clone(),
+                        actor.inputs_rate,
+                        actor.outputs_rate,
+                    ));
+                }
+                let throttled: Vec<usize> = plain_actors
+                    .iter()
+                    .filter_map(|actor| actor.outputs.as_ref())
+                    .flatten()
+                    .filter(|output| output.kind == OutputKind::Throttled)
+                    .flat_map(|output| output.channels.iter().copied())
+                    .collect();
+                for actor in plain_actors.iter().filter(|actor| actor.outputs.is_some()) {
+                    if let Some(input) = actor
+                        .inputs
+                        .iter()
+                        .flatten()
+                        .find(|input| throttled.contains(&input.channel))
+                    {
+                        return Err(ModelError::Throttled(
+                            actor.client.clone(),
+                            input.data.clone(),
+                        ));
+                    }
+                }
+                let outputs: Vec<usize> = plain_actors
+                    .iter()
+                    .filter_map(|actor| actor.outputs.as_ref())
+                    .flatten()
+                    .flat_map(|output| output.channels.iter().copied())
+                    .collect();
+                for actor in &plain_actors {
+                    if let Some(input) = actor
+                        .inputs
+                        .iter()
+                        .flatten()
+                        .find(|input| !outputs.contains(&input.channel))
+                    {
+                        return Err(ModelError::OrphanInput(
+                            actor.client.clone(),
+                            input.data.clone(),
+                        ));
+                    }
+                }
+                let inputs: Vec<usize> = plain_actors
+                    .iter()
+                    .filter_map(|actor| actor.inputs.as_ref())
+                    .flatten()
+                    .map(|input| input.channel)
+                    .collect();
+                for actor in &plain_actors {
+                    if let Some(output) = actor.outputs.iter().flatten().find(|output| {
+                        output
+                            .channels
+                            .iter()
+                            .any(|channel| !inputs.contains(channel))
+                    }) {
+                        return Err(ModelError::OrphanOutput(
+                            actor.client.clone(),
+                            output.data.clone(),
+                        ));
+                    }
+                }
+                if let Some(actor) = plain_actors.iter().find(|actor| {
+                    actor.inputs.iter().flatten().count() == 0
+                        && actor.outputs.iter().flatten().count() == 0
+                }) {
+                    return Err(ModelError::Unconnected(actor.client.clone()));
+                }
+                if let Some(cycle) = find_cycle(&data_flow(&plain_actors, true)) {
+                    return Err(ModelError::Cycle(
+                        cycle
+                            .into_iter()
+                            .map(|i| plain_actors[i].client.clone())
+                            .collect(),
+                    ));
+                }
+                if self.deterministic {
+                    step_ticks(&plain_actors)?;
+                }
                 Ok(Model::<Ready> {
                     name: self.name,
                     actors: self.actors,
                     task_handles: None,
                     state: PhantomData,
                     start: Instant::now(),
+                    deterministic: self.deterministic,
+                    steps: Vec::new(),
+                    summary: None,
+                    timeout: self.timeout,
+                    stall_timeout: self.stall_timeout,
+                    loop_times: self.loop_times,
+                    progress: Vec::new(),
+                    gate: Default::default(),
+                    nan_guard: self.nan_guard,
                 })
             }
             None => Err(ModelError::NoActors),
@@ -247,14 +1397,99 @@ impl Model<Unknown> {
 }
 
 impl Model<Ready> {
+    /**
+    Returns a [Stepper] that runs the model one step at a time
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{clients::Integrator, prelude::*};
+    enum U {};
+    enum Y {};
+    let mut source: Initiator<_> = Signals::new(1, 3).signals(Signal::Constant(1.)).into();
+    let mut integrator: Actor<_> = Integrator::<f64, U>::new(1).gain(0.5).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, U>().into_input(&mut integrator);
+    integrator.add_output().build::<Vec<f64>, Y>().into_input(&mut sink);
+    let mut stepper = Model::new(vec![Box::new(sink), Box::new(integrator), Box::new(source)])
+        .check()?
+        .into_stepper()?;
+    for (i, y) in [-0.5, -1., -1.5].into_iter().enumerate() {
+        stepper.step().await?;
+        assert_eq!(stepper.n_step(), i + 1);
+        assert_eq!(logging.lock().await.len(), i + 1);
+        assert_eq!(*logging.lock().await.last().unwrap(), y);
+    }
+    assert!(stepper.step().await.is_err());
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+
+    A step of a multi-rate model lasts the least common multiple of the actors rates:
+    the source below runs 5 times per step for the decimating sampler and the sink to run once
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum U {};
+    let mut source: Initiator<_> = Signals::new(1, 10).signals(Signal::Ramp { a: 1., b: 0. }).into();
+    let mut sampler: Actor<_, 1, 5> = Sampler::<Vec<f64>, U>::default().into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_, 5>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, U>().into_input(&mut sampler);
+    sampler.add_output().build::<Vec<f64>, U>().into_input(&mut sink);
+    let mut stepper = Model::new(vec![Box::new(sink), Box::new(sampler), Box::new(source)])
+        .check()?
+        .into_stepper()?;
+    assert_eq!(stepper.ticks(), 5);
+    stepper.step().await?;
+    assert_eq!(*logging.lock().await.as_slice(), [4.]);
+    stepper.step().await?;
+    assert_eq!(*logging.lock().await.as_slice(), [4., 9.]);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+
+    A bootstrapped output sent more than once per step cannot be stepped
+    and returns a [ModelError::MultiRateBootstrap] error.
+    */
+    pub fn into_stepper(mut self) -> Result<Stepper> {
+        Stepper::new(self.actors.take().unwrap())
+    }
     /// Spawns each actor task
+    ///
+    /// The actors of a [deterministic](Model::deterministic) model are stepped one after the other within a single task
     pub fn run(mut self) -> Model<Running> {
         let mut actors = self.actors.take().unwrap();
+        if self.loop_times {
+            actors
+                .iter()
+                .for_each(|actor| actor.progress().record_loop_times());
+        }
+        let steps = actors.iter().filter_map(|actor| actor.steps()).collect();
+        let progress = actors
+            .iter()
+            .map(|actor| (actor.as_plain().client, actor.progress()))
+            .collect();
+        let gate = Arc::new(Gate::default());
+        actors.iter_mut().for_each(|actor| actor.gate(gate.clone()));
         let mut task_handles = vec![];
-        while let Some(mut actor) = actors.pop() {
-            task_handles.push(tokio::spawn(async move {
-                actor.task().await;
+        if self.deterministic {
+            let stepper = Stepper::new(actors).expect("the deterministic model cannot be stepped");
+            let gate = gate.clone();
+            task_handles.push(tokio::task::spawn_blocking(move || {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_time()
+                    .build()
+                    .expect("failed to build the deterministic model runtime")
+                    .block_on(stepper.run(gate));
             }));
+        } else {
+            while let Some(mut actor) = actors.pop() {
+                task_handles.push(tokio::spawn(async move {
+                    actor.task().await;
+                }));
+            }
         }
         let now: DateTime<Local> = Local::now();
         println!(
@@ -271,38 +1506,677 @@ impl Model<Ready> {
             task_handles: Some(task_handles),
             state: PhantomData,
             start: Instant::now(),
+            deterministic: self.deterministic,
+            steps,
+            summary: None,
+            timeout: self.timeout,
+            stall_timeout: self.stall_timeout,
+            loop_times: self.loop_times,
+            progress,
+            gate,
+            nan_guard: self.nan_guard,
         }
     }
 }
 
 impl Model<Running> {
+    /**
+    Pauses the model
+
+    The actors stop at the top of their next loop iteration, the data already sent is kept in the channels
+    until the model is [resume](Model::resume)d and all the actors restart together.
+
+    # Example
+    The model is paused while the source is in the middle of step 10,
+    the source completes the step and then waits for the model to be resumed
+    ```
+    use dos_actors::{clients::Source, prelude::*};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Arc,
+        },
+        time::Duration,
+    };
+    enum Sig {};
+    let n_step = 100;
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let last_step = Arc::new(AtomicUsize::new(0));
+            let (reached_tx, mut reached_rx) = tokio::sync::mpsc::unbounded_channel();
+            let (release_tx, release_rx) = mpsc::channel::<()>();
+            let mut source: Initiator<_> = Source::<Sig>::new({
+                let last_step = last_step.clone();
+                move |step| {
+                    last_step.store(step, Ordering::SeqCst);
+                    if step == 10 {
+                        reached_tx.send(()).unwrap();
+                        tokio::task::block_in_place(|| release_rx.recv().unwrap());
+                    }
+                    (step < n_step).then(|| vec![step as f64])
+                }
+            })
+            .into();
+            let logging = Logging::<f64>::default().into_arcx();
+            let mut sink = Terminator::<_>::new(logging.clone());
+            source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+            let model = Model::new(vec![Box::new(source), Box::new(sink)])
+                .check()?
+                .run();
+            reached_rx.recv().await.unwrap();
+            model.pause();
+            assert!(model.is_paused());
+            release_tx.send(()).unwrap();
+            // however long the model is paused, the source is not stepped again
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(last_step.load(Ordering::SeqCst), 10);
+            assert!(model.n_step() <= 11);
+            model.resume();
+            let model = model.wait().await?;
+            assert_eq!(model.summary().unwrap().n_step, n_step);
+            assert_eq!(logging.lock().await.len(), n_step);
+            Ok::<(), dos_actors::model::ModelError>(())
+        })?;
+    # Ok::<(), dos_actors::model::ModelError>(())
+    ```
+    */
+    pub fn pause(&self) {
+        self.gate.pause();
+    }
+    /// Resumes a [pause](Model::pause)d model
+    pub fn resume(&self) {
+        self.gate.resume();
+    }
+    /// Returns `true` if the model is [pause](Model::pause)d
+    pub fn is_paused(&self) -> bool {
+        self.gate.is_paused()
+    }
+    /// Returns the # of simulation steps so far, i.e. the largest # of iterations of the [Initiator]s
+    pub fn n_step(&self) -> usize {
+        self.steps
+            .iter()
+            .map(|steps| steps.load(Ordering::Relaxed))
+            .max()
+            .unwrap_or_default()
+    }
+    // Returns a snapshot of the actors progress
+    fn progress(&self) -> Vec<ActorProgress> {
+        self.progress
+            .iter()
+            .map(|(name, progress)| ActorProgress {
+                name: name.clone(),
+                iterations: progress.iterations(),
+                idle: progress.idle(),
+            })
+            .collect()
+    }
     /// Waits for the task of each actor to finish
     pub async fn wait(mut self) -> Result<Model<Completed>> {
-        let task_handles = self.task_handles.take().unwrap();
-        for task_handle in task_handles.into_iter() {
-            task_handle.await?;
+        let mut task_handles = self.task_handles.take().unwrap();
+        match (self.timeout, self.stall_timeout) {
+            (None, None) => {
+                for task_handle in task_handles.into_iter() {
+                    task_handle.await?;
+                }
+            }
+            (timeout, stall_timeout) => {
+                let aborted = tokio::select! {
+                    results = join_all(task_handles.iter_mut()) => {
+                        results
+                            .into_iter()
+                            .collect::<std::result::Result<Vec<_>, _>>()?;
+                        None
+                    }
+                    Some(timeout) = async move {
+                        match timeout {
+                            Some(timeout) => tokio::time::sleep(timeout).await,
+                            None => futures::future::pending().await,
+                        }
+                        timeout
+                    } => Some(ModelError::Timeout {
+                        timeout,
+                        progress: self.progress(),
+                    }),
+                    stall_timeout = stalled(&self.progress, self.start, stall_timeout) => {
+                        Some(ModelError::Stalled {
+                            stall_timeout,
+                            progress: self.progress(),
+                        })
+                    }
+                };
+                if let Some(e) = aborted {
+                    task_handles
+                        .iter()
+                        .for_each(|task_handle| task_handle.abort());
+                    return Err(e);
+                }
+            }
+        }
+        if let Some(NonFinite {
+            actor,
+            output,
+            step,
+        }) = self.nan_guard.iter().find_map(|rx| rx.try_recv().ok())
+        {
+            return Err(ModelError::NonFinite {
+                actor,
+                output,
+                step,
+            });
         }
-        let elapsed_time = Instant::now().duration_since(self.start);
+        let elapsed = Instant::now().duration_since(self.start);
+        let n_step = self.n_step();
+        let summary = Summary {
+            elapsed,
+            n_step,
+            steps_per_second: n_step as f64 / elapsed.as_secs_f64(),
+        };
         let now: DateTime<Local> = Local::now();
         println!(
-            "[{}<{}>] COMPLETED in {}",
+            "[{}<{}>] COMPLETED: {}",
             self.name
                 .as_ref()
                 .unwrap_or(&String::from("Model"))
                 .to_uppercase(),
             now.to_rfc3339_opts(SecondsFormat::Secs, true),
-            humantime::format_duration(elapsed_time)
+            summary
         );
+        let loop_times = loop_times(&self.progress);
+        if !loop_times.is_empty() {
+            println!("Loop times:");
+            loop_times.iter().for_each(|h| print!("{h}"));
+        }
         Ok(Model::<Completed> {
             name: self.name,
             actors: None,
             task_handles: None,
             state: PhantomData,
             start: Instant::now(),
+            deterministic: self.deterministic,
+            steps: Vec::new(),
+            summary: Some(summary),
+            timeout: self.timeout,
+            stall_timeout: self.stall_timeout,
+            loop_times: self.loop_times,
+            progress: self.progress,
+            gate: Default::default(),
+            nan_guard: Vec::new(),
         })
     }
 }
 
+impl Model<Completed> {
+    /**
+    Returns the run summary: wall-clock time, # of steps and steps per second
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 1_000).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .run()
+        .wait()
+        .await?;
+    let summary = model.summary().unwrap();
+    assert_eq!(summary.n_step, 1_000);
+    assert!(summary.steps_per_second > 0.);
+    # Ok::<(), dos_actors::model::ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub fn summary(&self) -> Option<&Summary> {
+        self.summary.as_ref()
+    }
+    /// Returns the loop times histogram of each actor, empty if the model was not set to record the [histogram_of_loop_times](Model::histogram_of_loop_times)
+    pub fn loop_times(&self) -> Vec<LoopTimes> {
+        loop_times(&self.progress)
+    }
+}
+
+// Collects the loop times histograms of the actors which loop times are recorded
+fn loop_times(progress: &[(String, Arc<Progress>)]) -> Vec<LoopTimes> {
+    progress
+        .iter()
+        .filter_map(|(name, progress)| {
+            progress.loop_times().map(|counts| LoopTimes {
+                name: name.clone(),
+                counts,
+            })
+        })
+        .collect()
+}
+
+/**
+Step-by-step [Model] execution
+
+A step lasts the least common multiple of the actors inputs and outputs rates,
+within a step the actors run, following the data flow order, as many iterations of their loop
+as they fit in the step duration and the output channels are resized to hold all the samples sent within a step.
+The clients state can be inspected in-between steps through their shared [Arc]<[Mutex](tokio::sync::Mutex)> references.
+The bootstrapped outputs are sent before the first step.
+*/
+pub struct Stepper {
+    actors: Vec<Option<Box<dyn Task>>>,
+    iterations: Vec<usize>,
+    ticks: usize,
+    n_step: usize,
+}
+impl Stepper {
+    // Sorts the actors following the data flow and resizes their outputs to hold the samples sent within a step
+    fn new(actors: Actors) -> Result<Self> {
+        let mut actors = topological_order(actors);
+        let plain_actors: Vec<_> = actors.iter().map(|actor| actor.as_plain()).collect();
+        let ticks = step_ticks(&plain_actors)?;
+        let receivers: Vec<_> = actors
+            .iter_mut()
+            .flat_map(|actor| actor.resize_outputs(Some(ticks)))
+            .collect();
+        for (id, rx) in receivers {
+            actors
+                .iter_mut()
+                .try_fold(rx, |rx, actor| actor.rewire_input(id, rx));
+        }
+        let iterations = plain_actors
+            .iter()
+            .map(|actor| match (actor.inputs_rate, actor.outputs_rate) {
+                (0, 0) => 1,
+                (0, no) => ticks / no,
+                (ni, no) if no >= ni && no.is_multiple_of(ni) => ticks / no,
+                (ni, _) => ticks / ni,
+            })
+            .collect();
+        Ok(Self {
+            actors: actors.into_iter().map(Some).collect(),
+            iterations,
+            ticks,
+            n_step: 0,
+        })
+    }
+    /**
+    Advances all the actors by one step
+
+    Returns an error once any of the actors loop has ended with an error,
+    the actors which loop has ended are [completed](crate::Actor::on_complete) and dropped
+
+    # Example
+    The source loop ends after its last sample and the step returns its error,
+    the sink loop ends, and the sink completes, on the next call as its input is dropped
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::{model::ModelError, prelude::*, ActorError};
+    use tokio::sync::oneshot;
+    enum Sig {};
+    let mut source: Initiator<_> = Signals::new(1, 3).into();
+    let (tx, mut rx) = oneshot::channel();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx()).on_complete(tx);
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let mut stepper = Model::new(vec![Box::new(source), Box::new(sink)])
+        .check()?
+        .into_stepper()?;
+    for _ in 0..3 {
+        stepper.step().await?;
+    }
+    assert!(matches!(
+        stepper.step().await,
+        Err(ModelError::Step(4, ActorError::Disconnected(_)))
+    ));
+    assert!(rx.try_recv().is_err());
+    assert!(matches!(
+        stepper.step().await,
+        Err(ModelError::Step(4, ActorError::DropRecv(_)))
+    ));
+    assert!(rx.try_recv().is_ok());
+    # Ok::<(), ModelError>(())
+    # }).unwrap();
+    ```
+    */
+    pub async fn step(&mut self) -> Result<()> {
+        if self.n_step == 0 {
+            for slot in self.actors.iter_mut() {
+                if let Some(actor) = slot.as_mut() {
+                    if let Err(e) = actor.init().await {
+                        actor.complete(Ok(()));
+                        *slot = None;
+                        return Err(ModelError::Step(0, e));
+                    }
+                }
+            }
+        }
+        for (actor, &n) in self.actors.iter_mut().zip(&self.iterations) {
+            if let Some(result) = step_actor(actor, n).await {
+                if let Some(mut actor) = actor.take() {
+                    actor.complete(Ok(()));
+                }
+                result.map_err(|e| ModelError::Step(self.n_step + 1, e))?;
+            }
+        }
+        self.n_step += 1;
+        Ok(())
+    }
+    /// Returns the # of completed steps
+    pub fn n_step(&self) -> usize {
+        self.n_step
+    }
+    /// Returns the duration of a step in sampling ticks
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+    // Steps the model until the loops of all the actors have ended
+    async fn run(mut self, gate: Arc<Gate>) {
+        for slot in self.actors.iter_mut() {
+            if let Some(actor) = slot.as_mut() {
+                if let Err(e) = actor.init().await {
+                    let who = actor.as_plain().client;
+                    crate::print_error(format!("{} bootstrapping failed", who), &e);
+                    actor.complete(Ok(()));
+                    *slot = None;
+                }
+            }
+        }
+        while self.actors.iter().any(Option::is_some) {
+            gate.wait().await;
+            for (actor, &n) in self.actors.iter_mut().zip(&self.iterations) {
+                if let Some(result) = step_actor(actor, n).await {
+                    if let Some(mut actor) = actor.take() {
+                        actor.complete(result);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Runs the `n` iterations of an actor within a step and returns the loop result once the actor loop has ended
+async fn step_actor(
+    actor: &mut Option<Box<dyn Task>>,
+    n: usize,
+) -> Option<std::result::Result<(), crate::ActorError>> {
+    let actor = actor.as_mut()?;
+    for _ in 0..n {
+        match actor.step().await {
+            Ok(false) => (),
+            Ok(true) => return Some(Ok(())),
+            Err(e) => return Some(Err(e)),
+        }
+    }
+    None
+}
+
+// Returns the duration of a model step in sampling ticks: the least common multiple of the actors rates
+fn step_ticks(plain_actors: &[PlainActor]) -> Result<usize> {
+    let ticks = plain_actors
+        .iter()
+        .flat_map(|actor| [actor.inputs_rate, actor.outputs_rate])
+        .filter(|&rate| rate > 0)
+        .fold(1, lcm);
+    for actor in plain_actors {
+        if let Some(output) =
+            actor.outputs.iter().flatten().find(|output| {
+                output.kind == OutputKind::Bootstrap && ticks / actor.outputs_rate > 1
+            })
+        {
+            return Err(ModelError::MultiRateBootstrap(
+                actor.client.clone(),
+                output.data.clone(),
+                ticks / actor.outputs_rate,
+            ));
+        }
+    }
+    Ok(ticks)
+}
+
+// Least common multiple
+fn lcm(a: usize, b: usize) -> usize {
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+    a / gcd(a, b) * b
+}
+
+// Returns `stall_timeout` once none of the clients has been updated for `stall_timeout`,
+// never returns if `stall_timeout` is [None]
+async fn stalled(
+    progress: &[(String, Arc<Progress>)],
+    start: Instant,
+    stall_timeout: Option<Duration>,
+) -> Duration {
+    let Some(stall_timeout) = stall_timeout else {
+        return futures::future::pending().await;
+    };
+    loop {
+        let idle = progress
+            .iter()
+            .filter_map(|(_, progress)| progress.idle())
+            .min()
+            .unwrap_or_else(|| start.elapsed());
+        if idle >= stall_timeout {
+            break stall_timeout;
+        }
+        tokio::time::sleep(stall_timeout - idle).await;
+    }
+}
+
+// Returns, for each actor, the indices of the actors receiving its outputs, bootstrapped outputs excepted,
+// the optional inputs are ignored too if `blocking` as they do not wait for the data
+fn data_flow(plain_actors: &[PlainActor], blocking: bool) -> Vec<Vec<usize>> {
+    plain_actors
+        .iter()
+        .enumerate()
+        .map(|(i, actor)| {
+            let channels: Vec<usize> = actor
+                .outputs
+                .iter()
+                .flatten()
+                .filter(|output| output.kind != OutputKind::Bootstrap)
+                .flat_map(|output| output.channels.iter().copied())
+                .collect();
+            plain_actors
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| {
+                    *j != i
+                        && other
+                            .inputs
+                            .iter()
+                            .flatten()
+                            .filter(|input| !(blocking && input.optional))
+                            .any(|input| channels.contains(&input.channel))
+                })
+                .map(|(j, _)| j)
+                .collect()
+        })
+        .collect()
+}
+// Returns the actors of a feedback loop without bootstrapped outputs, if any
+fn find_cycle(edges: &[Vec<usize>]) -> Option<Vec<usize>> {
+    // depth first search: 0 unvisited, 1 on the current path, 2 done
+    fn visit(i: usize, edges: &[Vec<usize>], state: &mut [u8], path: &mut Vec<usize>) -> bool {
+        state[i] = 1;
+        path.push(i);
+        for &j in &edges[i] {
+            if state[j] == 1 {
+                let k = path.iter().position(|&p| p == j).unwrap();
+                path.drain(..k);
+                return true;
+            }
+            if state[j] == 0 && visit(j, edges, state, path) {
+                return true;
+            }
+        }
+        state[i] = 2;
+        path.pop();
+        false
+    }
+    let mut state = vec![0u8; edges.len()];
+    let mut path = Vec::new();
+    (0..edges.len()).find_map(|i| {
+        (state[i] == 0 && visit(i, edges, &mut state, &mut path)).then(|| path.clone())
+    })
+}
+// Sorts the actors such that each actor comes after the actors it receives data from,
+// the bootstrapped outputs are ignored and cycles are broken following the actors order in the model
+fn topological_order(actors: Actors) -> Actors {
+    let plain_actors: Vec<_> = actors.iter().map(|actor| actor.as_plain()).collect();
+    let edges = data_flow(&plain_actors, false);
+    let mut in_degree = vec![0usize; actors.len()];
+    edges.iter().flatten().for_each(|&j| in_degree[j] += 1);
+    let mut order = Vec::with_capacity(actors.len());
+    let mut done = vec![false; actors.len()];
+    while order.len() < actors.len() {
+        let i = (0..actors.len())
+            .find(|&i| !done[i] && in_degree[i] == 0)
+            .or_else(|| (0..actors.len()).find(|&i| !done[i]))
+            .unwrap();
+        done[i] = true;
+        order.push(i);
+        edges[i]
+            .iter()
+            .for_each(|&j| in_degree[j] = in_degree[j].saturating_sub(1));
+    }
+    let mut actors: Vec<_> = actors.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| actors[i].take()).collect()
+}
+
+/// [Model] actor description
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopologyActor {
+    /// Actor name or client type if the actor is not named
+    pub tag: String,
+    /// Client type
+    pub client: String,
+    /// Inputs rate `NI`
+    pub inputs_rate: usize,
+    /// Outputs rate `NO`
+    pub outputs_rate: usize,
+}
+/// [Model] connection between 2 actors
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopologyEdge {
+    /// Tag of the actor the data is sent from
+    pub producer: String,
+    /// Tag of the actor the data is sent to
+    pub consumer: String,
+    /// Data UID type
+    pub data: String,
+    /// Data rate
+    pub rate: usize,
+    /// Bootstrapped output
+    pub bootstrap: bool,
+    /// Channel capacity, [usize::MAX] if unbounded
+    pub capacity: usize,
+}
+/// [Model] connection between an actor output and the actors receiving it
+///
+/// The connections are returned by [Model::connections()]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Connection {
+    /// Tag of the actor the data is sent from
+    pub producer: String,
+    /// Tags of the actors the data is sent to
+    pub consumers: Vec<String>,
+    /// Data UID type
+    pub data: String,
+    /// Output label
+    pub label: Option<String>,
+    /// Data rate
+    pub rate: usize,
+    /// Bootstrapped output
+    pub bootstrap: bool,
+    /// Channel capacity, [usize::MAX] if unbounded
+    pub capacity: usize,
+}
+// Returns the connections of each actor output
+fn connections(actors: &[PlainActor]) -> Vec<Connection> {
+    actors
+        .iter()
+        .flat_map(|producer| {
+            producer
+                .outputs
+                .iter()
+                .flatten()
+                .map(|output| Connection {
+                    producer: producer.client.clone(),
+                    consumers: actors
+                        .iter()
+                        .filter(|consumer| {
+                            consumer
+                                .inputs
+                                .iter()
+                                .flatten()
+                                .any(|input| output.channels.contains(&input.channel))
+                        })
+                        .map(|consumer| consumer.client.clone())
+                        .collect(),
+                    data: output.data.clone(),
+                    label: output.label.clone(),
+                    rate: producer.outputs_rate,
+                    bootstrap: output.kind == OutputKind::Bootstrap,
+                    capacity: output.capacity,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// [Model] actors and connections
+///
+/// A new [Topology] is created with [Model::topology()]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Topology {
+    pub actors: Vec<TopologyActor>,
+    pub edges: Vec<TopologyEdge>,
+}
+impl Topology {
+    fn new(actors: Vec<PlainActor>) -> Self {
+        let edges = connections(&actors)
+            .into_iter()
+            .flat_map(|connection| {
+                connection
+                    .consumers
+                    .into_iter()
+                    .map(|consumer| TopologyEdge {
+                        producer: connection.producer.clone(),
+                        consumer,
+                        data: connection.data.clone(),
+                        rate: connection.rate,
+                        bootstrap: connection.bootstrap,
+                        capacity: connection.capacity,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Self {
+            actors: actors
+                .into_iter()
+                .map(|actor| TopologyActor {
+                    tag: actor.client,
+                    client: actor.client_type,
+                    inputs_rate: actor.inputs_rate,
+                    outputs_rate: actor.outputs_rate,
+                })
+                .collect(),
+            edges,
+        }
+    }
+}
+
 /// [Model] network mapping
 ///
 /// The structure is used to build a [Graphviz](https://www.graphviz.org/) diagram of a [Model].
@@ -312,6 +2186,7 @@ impl Model<Running> {
 #[derive(Debug)]
 pub struct Graph {
     actors: Vec<PlainActor>,
+    rates: bool,
 }
 impl Graph {
     fn new(actors: Vec<PlainActor>) -> Self {
@@ -328,11 +2203,24 @@ impl Graph {
                 .unwrap()
                 .to_string();
         });
-        Self { actors }
+        Self {
+            actors,
+            rates: false,
+        }
+    }
+    /// Annotates the actors with their inputs and outputs rates `(NI, NO)`
+    ///
+    /// The outputs of decimating actors (`NO>NI`) are drawn with dotted lines
+    /// and the outputs of upsampling actors (`NO<NI`) are drawn with thick lines
+    pub fn rates(self) -> Self {
+        Self {
+            rates: true,
+            ..self
+        }
     }
     /// Returns the diagram in the [Graphviz](https://www.graphviz.org/) dot language
     pub fn to_string(&self) -> String {
-        use PlainOutput::*;
+        use OutputKind::*;
         let mut lookup: BTreeMap<usize, usize> = BTreeMap::new();
         let mut colors = (1usize..=8).cycle();
         let outputs: Vec<_> = self
@@ -346,19 +2234,43 @@ impl Graph {
                             let color = lookup
                                 .entry(actor.outputs_rate)
                                 .or_insert_with(|| colors.next().unwrap());
-                            match output {
-                                Bootstrap(output) => format!(
-                                    "{0} -> {1} [color={2}, style=bold];",
+                            let mut style = match output.kind {
+                                Bootstrap => vec!["bold"],
+                                Regular => vec![],
+                                Throttled => vec!["dashed"],
+                            };
+                            let mut attributes = String::new();
+                            if self.rates && actor.inputs_rate > 0 {
+                                if actor.outputs_rate > actor.inputs_rate {
+                                    style.push("dotted");
+                                }
+                                if actor.outputs_rate < actor.inputs_rate {
+                                    attributes.push_str(", penwidth=3");
+                                }
+                            }
+                            if let Some(label) = output.label.as_ref() {
+                                attributes.push_str(&format!(
+                                    r#", label="{}", fontsize=9, fontcolor=lightgray"#,
+                                    label.replace('"', r#"\""#)
+                                ));
+                            }
+                            if style.is_empty() {
+                                format!(
+                                    "{0} -> {1} [color={2}{3}];",
                                     actor.client,
-                                    output.split("::").last().unwrap(),
-                                    color
-                                ),
-                                Regular(output) => format!(
-                                    "{0} -> {1} [color={2}];",
+                                    output.data.split("::").last().unwrap(),
+                                    color,
+                                    attributes
+                                )
+                            } else {
+                                format!(
+                                    r#"{0} -> {1} [color={2}, style="{3}"{4}];"#,
                                     actor.client,
-                                    output.split("::").last().unwrap(),
-                                    color
-                                ),
+                                    output.data.split("::").last().unwrap(),
+                                    color,
+                                    style.join(","),
+                                    attributes
+                                )
                             }
                         })
                         .collect::<Vec<String>>()
@@ -379,7 +2291,7 @@ impl Graph {
                                 .or_insert_with(|| colors.next().unwrap());
                             format!(
                                 r#"{0} -> {1} [label="{0}", color={2}];"#,
-                                input.split("::").last().unwrap(),
+                                input.data.split("::").last().unwrap(),
                                 actor.client,
                                 color
                             )
@@ -412,8 +2324,15 @@ digraph  G {{
 "#,
             self.actors
                 .iter()
-                .map(|actor| actor.client.as_str())
-                .collect::<Vec<&str>>()
+                .map(|actor| if self.rates {
+                    format!(
+                        r#"{0} [label="{0}\n({1}, {2})"]"#,
+                        actor.client, actor.inputs_rate, actor.outputs_rate
+                    )
+                } else {
+                    actor.client.clone()
+                })
+                .collect::<Vec<String>>()
                 .join("; "),
             outputs.join("\n"),
             inputs.join("\n"),
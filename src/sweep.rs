@@ -0,0 +1,278 @@
+/*!
+# Parameter sweeps
+
+Helpers to run a model repeatedly over a range of parameters.
+*/
+
+use crate::{
+    clients::Logging,
+    model::{Completed, Model, ModelError, Unknown},
+};
+use futures::{stream, Future, StreamExt};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tokio::sync::Mutex;
+
+/**
+Tunes a model parameter
+
+For each `gain`, the model is built with `build` which returns the model and the [Logging] of the data the metric is computed from,
+the model is run and `metric` is evaluated on the [Logging].
+Returns the gain with the smallest metric and the metric value, [None] if there are no gains.
+
+# Example
+Tuning the gain of an integral controller rejecting a constant disturbance
+```
+# tokio_test::block_on(async {
+use dos_actors::{
+    clients::Integrator,
+    io::{Data, Read, Write},
+    prelude::*,
+    sweep::tune,
+    Update,
+};
+use std::sync::Arc;
+enum Disturbance {};
+enum Command {};
+enum Residual {};
+// Plant adding the control command to the disturbance
+#[derive(Default)]
+struct Plant {
+    disturbance: f64,
+    command: f64,
+}
+impl Update for Plant {}
+impl Read<Vec<f64>, Disturbance> for Plant {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, Disturbance>>) {
+        self.disturbance = data[0];
+    }
+}
+impl Read<Vec<f64>, Command> for Plant {
+    fn read(&mut self, data: Arc<Data<Vec<f64>, Command>>) {
+        self.command = data[0];
+    }
+}
+impl Write<Vec<f64>, Residual> for Plant {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, Residual>>> {
+        Some(Arc::new(Data::new(vec![self.disturbance + self.command])))
+    }
+}
+let gains: Vec<f64> = (1..20).map(|i| i as f64 * 0.1).collect();
+let (gain, _) = tune(
+    |gain| {
+        let mut disturbance: Initiator<_> = Signals::new(1, 20).signals(Signal::Constant(1.)).into();
+        let mut plant: Actor<_> = Plant::default().into();
+        let mut integrator: Actor<_> = Integrator::<f64, Residual>::new(1).gain(gain).into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        disturbance
+            .add_output()
+            .build::<Vec<f64>, Disturbance>()
+            .into_input(&mut plant);
+        plant
+            .add_output()
+            .multiplex(2)
+            .build::<Vec<f64>, Residual>()
+            .into_input(&mut integrator)
+            .into_input(&mut sink);
+        integrator
+            .add_output()
+            .bootstrap()
+            .build::<Vec<f64>, Command>()
+            .into_input(&mut plant);
+        let model = Model::new(vec![
+            Box::new(disturbance),
+            Box::new(plant),
+            Box::new(integrator),
+            Box::new(sink),
+        ]);
+        (model, logging)
+    },
+    gains,
+    |logging| logging.iter().map(|x| x * x).sum(),
+)
+.await?
+.unwrap();
+// deadbeat control
+assert!((gain - 1.).abs() < 1e-6, "gain: {gain}");
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+*/
+pub async fn tune<B, G, M>(build: B, gains: G, metric: M) -> Result<Option<(f64, f64)>, ModelError>
+where
+    B: Fn(f64) -> (Model<Unknown>, Arc<Mutex<Logging<f64>>>),
+    G: IntoIterator<Item = f64>,
+    M: Fn(&Logging<f64>) -> f64,
+{
+    let mut best: Option<(f64, f64)> = None;
+    for gain in gains {
+        let (model, logging) = build(gain);
+        model.check()?.run().wait().await?;
+        let value = metric(&*logging.lock().await);
+        log::info!("gain: {gain}, metric: {value}");
+        match best {
+            Some((_, best_value)) if value >= best_value => (),
+            _ => best = Some((gain, value)),
+        }
+    }
+    Ok(best)
+}
+
+/**
+Runs `n` independent instances of a model concurrently
+
+The model instance #`i` is built with `build(i)` which returns the model and the data the metric is computed from,
+e.g. the [Logging] of a model output,
+each instance must own all its clients, sharing clients between instances would couple them.
+At most `concurrency` instances are running at the same time, their actors are spawned across the tokio runtime.
+Returns the `metric` evaluated on each completed instance and on its data, in the order of the instances.
+
+If an instance fails, no other instance is started, the instances already running are waited for
+and the error of the first failed instance is returned.
+
+# Example
+8 instances of a model differing only by its seed
+```
+# tokio_test::block_on(async {
+use dos_actors::{
+    io::{Data, Write},
+    model::Completed,
+    prelude::*,
+    sweep::sweep,
+    Update,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+enum Sig {};
+// Source of 100 pseudo-random samples in [0,1[ drawn from its seed
+#[derive(Default)]
+struct Random {
+    state: u64,
+    n_sample: usize,
+}
+impl Update for Random {
+    fn seed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+    fn update(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.n_sample += 1;
+    }
+    fn finished(&self) -> bool {
+        self.n_sample > 100
+    }
+}
+impl Write<Vec<f64>, Sig> for Random {
+    fn write(&mut self) -> Option<Arc<Data<Vec<f64>, Sig>>> {
+        let sample = (self.state >> 11) as f64 / (1u64 << 53) as f64;
+        Some(Arc::new(Data::new(vec![sample])))
+    }
+}
+let build = |i: usize| {
+    let mut source: Initiator<_> = Random::default().into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(sink)]).seed(i as u64);
+    (model, logging)
+};
+let mean = |_: Model<Completed>, logging: Arc<Mutex<Logging<f64>>>| async move {
+    let logging = logging.lock().await;
+    logging.iter().sum::<f64>() / logging.len() as f64
+};
+let means = sweep(8, 4, build, mean).await?;
+assert_eq!(means.len(), 8);
+// the instances are independent: each seed gives a different metric
+for (i, a) in means.iter().enumerate() {
+    assert!(means[i + 1..].iter().all(|b| a != b));
+}
+// the means of 100 uniform samples are within a few standard deviations (0.029) of 0.5
+assert!(means.iter().all(|m| (m - 0.5).abs() < 0.15), "{means:?}");
+// and the same seeds give the same metrics
+assert_eq!(sweep(8, 2, build, mean).await?, means);
+# Ok::<(), dos_actors::model::ModelError>(())
+# }).unwrap();
+```
+
+An instance failing while another one is running: the running instance completes and the next ones are not built
+```
+# tokio_test::block_on(async {
+use dos_actors::{model::ModelError, prelude::*, sweep::sweep};
+use std::sync::{Arc, Mutex};
+enum Sig {};
+let built = Arc::new(Mutex::new(vec![]));
+let build = |i: usize| {
+    built.lock().unwrap().push(i);
+    // the first instance produces a NaN
+    let mut samples = vec![1f64; 100];
+    if i == 0 {
+        samples[10] = f64::NAN;
+    }
+    let mut source: Initiator<_> = Signals::new(1, 100).signals(Signal::Sequence(samples)).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+    let model = Model::new(vec![Box::new(source), Box::new(sink)]).nan_guard();
+    (model, logging)
+};
+let loggings = Arc::new(Mutex::new(vec![]));
+let result = sweep(4, 2, build, |_, logging| {
+    let loggings = loggings.clone();
+    async move { loggings.lock().unwrap().push(logging) }
+})
+.await;
+assert!(matches!(result, Err(ModelError::NonFinite { .. })));
+assert_eq!(*built.lock().unwrap(), vec![0, 1]);
+let loggings = loggings.lock().unwrap();
+assert_eq!(loggings.len(), 1);
+assert_eq!(loggings[0].try_lock().unwrap().len(), 100);
+# });
+```
+*/
+pub async fn sweep<B, D, M, F, R>(
+    n: usize,
+    concurrency: usize,
+    build: B,
+    metric: M,
+) -> Result<Vec<R>, ModelError>
+where
+    B: Fn(usize) -> (Model<Unknown>, D),
+    M: Fn(Model<Completed>, D) -> F,
+    F: Future<Output = R>,
+{
+    let failed = AtomicBool::new(false);
+    let results: Vec<_> = stream::iter(0..n)
+        .map(|i| {
+            let (build, metric, failed) = (&build, &metric, &failed);
+            async move {
+                if failed.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let (model, data) = build(i);
+                let completed = match model.check() {
+                    Ok(model) => model.run().wait().await,
+                    Err(e) => Err(e),
+                };
+                match completed {
+                    Ok(model) => {
+                        log::info!("instance #{i} completed");
+                        Some(Ok(metric(model, data).await))
+                    }
+                    Err(e) => {
+                        failed.store(true, Ordering::Relaxed);
+                        Some(Err(e))
+                    }
+                }
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    results.into_iter().flatten().collect()
+}
@@ -0,0 +1,100 @@
+/*!
+# Test utilities
+
+Helpers for testing integrated models, the module is enabled with the `test-utils` feature.
+*/
+
+use crate::{
+    clients::Logging,
+    model::{Model, ModelError, Unknown},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeterminismError {
+    #[error("model run failed")]
+    Model(#[from] ModelError),
+    #[error("the runs logged {0} and {1} values")]
+    Length(usize, usize),
+    #[error("the runs diverge at sample #{step}, value #{index}: {first} != {second}")]
+    Divergence {
+        step: usize,
+        index: usize,
+        first: f64,
+        second: f64,
+    },
+}
+
+impl Model<Unknown> {
+    /**
+    Checks that a model produces the same data from one run to the next
+
+    The model is built twice with `build` which returns the model and the [Logging] of its outputs,
+    each model is run in [deterministic](Model::deterministic) mode and the data of both [Logging]s are compared sample by sample.
+    Returns an error with the first divergent value if the data differ.
+
+    # Example
+    ```
+    # tokio_test::block_on(async {
+    use dos_actors::prelude::*;
+    enum Sig {};
+    Model::validate_determinism(|| {
+        let mut source: Initiator<_> = Signals::new(1, 100)
+            .signals(Signal::Sinusoid {
+                amplitude: 1.,
+                sampling_frequency_hz: 100.,
+                frequency_hz: 5.,
+                phase_s: 0.,
+            })
+            .into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source.add_output().build::<Vec<f64>, Sig>().into_input(&mut sink);
+        (Model::new(vec![Box::new(source), Box::new(sink)]), logging)
+    })
+    .await?;
+    # Ok::<(), dos_actors::testing::DeterminismError>(())
+    # }).unwrap();
+    ```
+    */
+    pub async fn validate_determinism<F>(build: F) -> Result<(), DeterminismError>
+    where
+        F: Fn() -> (Model<Unknown>, Arc<Mutex<Logging<f64>>>),
+    {
+        let first = Self::run_logged(&build).await?;
+        let second = Self::run_logged(&build).await?;
+        let logging = first.lock().await;
+        let other_logging = second.lock().await;
+        if logging.len() != other_logging.len() || logging.n_data() != other_logging.n_data() {
+            return Err(DeterminismError::Length(
+                logging.as_slice().len(),
+                other_logging.as_slice().len(),
+            ));
+        }
+        let n_data = logging.n_data().max(1);
+        match logging
+            .iter()
+            .zip(other_logging.iter())
+            .enumerate()
+            .find(|(_, (x, y))| x.to_bits() != y.to_bits())
+        {
+            Some((k, (&first, &second))) => Err(DeterminismError::Divergence {
+                step: k / n_data,
+                index: k % n_data,
+                first,
+                second,
+            }),
+            None => Ok(()),
+        }
+    }
+    // Runs the model built with `build` and returns its logs
+    async fn run_logged<F>(build: &F) -> Result<Arc<Mutex<Logging<f64>>>, ModelError>
+    where
+        F: Fn() -> (Model<Unknown>, Arc<Mutex<Logging<f64>>>),
+    {
+        let (model, logging) = build();
+        model.deterministic().check()?.run().wait().await?;
+        Ok(logging)
+    }
+}
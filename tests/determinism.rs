@@ -0,0 +1,51 @@
+//! Model::validate_determinism on deterministic and non-deterministic models
+
+use dos_actors::{prelude::*, testing::DeterminismError};
+
+enum Sig {}
+
+#[tokio::test]
+async fn deterministic() -> anyhow::Result<()> {
+    Model::validate_determinism(|| {
+        let mut source: Initiator<_> = Signals::new(2, 100)
+            .signals(Signal::Ramp { a: 0.5, b: 1. })
+            .into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source
+            .add_output()
+            .build::<Vec<f64>, Sig>()
+            .into_input(&mut sink);
+        (Model::new(vec![Box::new(source), Box::new(sink)]), logging)
+    })
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn thread_rng() {
+    let error = Model::validate_determinism(|| {
+        let mut source: Initiator<_> = Signals::new(2, 100)
+            .signals(Signal::white_noise().unwrap())
+            .into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut sink = Terminator::<_>::new(logging.clone());
+        source
+            .add_output()
+            .build::<Vec<f64>, Sig>()
+            .into_input(&mut sink);
+        (Model::new(vec![Box::new(source), Box::new(sink)]), logging)
+    })
+    .await
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("the runs diverge at sample #0, value #0"),
+        "{error}"
+    );
+    match error {
+        DeterminismError::Divergence { first, second, .. } => assert_ne!(first, second),
+        _ => panic!("expected the runs to diverge"),
+    }
+}
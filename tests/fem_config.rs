@@ -0,0 +1,44 @@
+//! FEM inputs/outputs selection from a config file
+//!
+//! The FEM model repository is read from the `FEM_REPO` environment variable
+
+use dos_actors::clients::fem::FemIoConfig;
+use fem::{
+    dos::{DiscreteModalSolver, ExponentialMatrix},
+    FEM,
+};
+
+#[test]
+fn fem_config() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("fem_io.json");
+    std::fs::write(
+        &path,
+        r#"{
+  "inputs": ["OSSElDriveTorque", "OSSAzDriveTorque", "OSSRotDriveTorque"],
+  "outputs": ["OSSM1Lcl", "MCM2Lcl6D"]
+}"#,
+    )?;
+    let config = FemIoConfig::from_json(&path)?;
+
+    let fem = FEM::from_env()?.static_from_env()?;
+    let state_space = config
+        .select(
+            DiscreteModalSolver::<ExponentialMatrix>::from_fem(fem)
+                .sampling(1e3)
+                .proportional_damping(2. / 100.),
+        )?
+        .build()?;
+    assert_eq!(state_space.u.len(), 12 + 4 + 4);
+    assert_eq!(state_space.y.len(), 42 + 42);
+    Ok(())
+}
+
+#[test]
+fn fem_config_unknown_io() -> anyhow::Result<()> {
+    let config: FemIoConfig = serde_json::from_str(r#"{"inputs": ["OSSDrive"], "outputs": []}"#)?;
+    let fem = FEM::from_env()?.static_from_env()?;
+    assert!(config
+        .select(DiscreteModalSolver::<ExponentialMatrix>::from_fem(fem))
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! M1 segments actuators created and connected by iterating over the segments
+
+use dos_actors::{
+    clients::m1::actuators::segments,
+    io::{Data, Read},
+    prelude::*,
+    Update,
+};
+use std::sync::Arc;
+
+// Stand-in for the FEM actor
+struct Fem;
+impl Update for Fem {}
+impl<U> Read<Vec<f64>, U> for Fem {
+    fn read(&mut self, _data: Arc<Data<Vec<f64>, U>>) {}
+}
+
+#[test]
+fn m1_segments() {
+    const M1_RATE: usize = 10;
+    let mut load_cells: Actor<_, 1, M1_RATE> = m1_ctrl::hp_load_cells::Controller::new().into();
+    let mut fem: Actor<_> = Fem.into();
+    let mut actors = segments(&mut load_cells, &mut fem);
+    assert_eq!(actors.len(), 7);
+    actors.push(Box::new(load_cells));
+    actors.push(Box::new(fem));
+
+    let topology = Model::new(actors).topology().unwrap();
+    for sid in 1..=7 {
+        let segment = format!("m1_ctrl::actuators::segment{sid}::Controller");
+        let to_fem: Vec<_> = topology
+            .edges
+            .iter()
+            .filter(|edge| edge.producer == segment)
+            .collect();
+        assert_eq!(to_fem.len(), 1);
+        assert!(to_fem[0].consumer.ends_with("Fem"));
+        assert!(to_fem[0]
+            .data
+            .ends_with(&format!("M1ActuatorsSegment{sid}")));
+        assert!(to_fem[0].bootstrap);
+        assert!(topology
+            .edges
+            .iter()
+            .any(|edge| edge.consumer == segment && edge.data.ends_with(&format!("S{sid}HPLC"))));
+    }
+}
@@ -0,0 +1,123 @@
+//! Model errors detected by Model::check and Model::wait
+
+use dos_actors::{clients::Integrator, model::ModelError, prelude::*};
+use std::time::Duration;
+
+enum Sig {}
+enum U {}
+enum Y {}
+
+#[test]
+fn rate_mismatch() {
+    let mut source: Initiator<_, 2> = Signals::new(1, 100).into();
+    let mut sampler: Actor<_, 2, 3> = (Sampler::<Vec<f64>, Sig>::default(), "sampler").into();
+    let mut sink = Terminator::<_, 3>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sampler);
+    sampler
+        .add_output()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    let check = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)]).check();
+    assert!(
+        matches!(check, Err(ModelError::NonIntegerRateRatio(actor, 2, 3)) if actor == "sampler")
+    );
+}
+
+#[test]
+fn cycle() {
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut a: Actor<_> = (Integrator::<f64, Sig>::new(1), "A").into();
+    let mut b: Actor<_> = (Integrator::<f64, U>::new(1), "B").into();
+    let mut c: Actor<_> = (Integrator::<f64, Y>::new(1), "C").into();
+    source
+        .add_output()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut a);
+    a.add_output().build::<Vec<f64>, U>().into_input(&mut b);
+    b.add_output().build::<Vec<f64>, Y>().into_input(&mut c);
+    c.add_output().build::<Vec<f64>, U>().into_input(&mut b);
+    let check = Model::new(vec![
+        Box::new(source),
+        Box::new(a),
+        Box::new(b),
+        Box::new(c),
+    ])
+    .check();
+    match check {
+        Err(ModelError::Cycle(actors)) => assert_eq!(actors, vec!["B", "C"]),
+        _ => panic!("the feedback loop is not detected"),
+    }
+}
+
+#[test]
+fn bootstrapped_cycle() {
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut a: Actor<_> = (Integrator::<f64, Sig>::new(1), "A").into();
+    let mut b: Actor<_> = (Integrator::<f64, U>::new(1), "B").into();
+    let mut c: Actor<_> = (Integrator::<f64, Y>::new(1), "C").into();
+    source
+        .add_output()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut a);
+    a.add_output().build::<Vec<f64>, U>().into_input(&mut b);
+    b.add_output().build::<Vec<f64>, Y>().into_input(&mut c);
+    c.add_output()
+        .bootstrap()
+        .build::<Vec<f64>, U>()
+        .into_input(&mut b);
+    let check = Model::new(vec![
+        Box::new(source),
+        Box::new(a),
+        Box::new(b),
+        Box::new(c),
+    ])
+    .check();
+    assert!(!matches!(check, Err(ModelError::Cycle(_))));
+}
+
+#[tokio::test]
+async fn stalled() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source
+        .add_output()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    // the sink cannot update its client while the logger is locked here
+    let held = logging.lock().await;
+    let result = Model::new(vec![Box::new(source), Box::new(sink)])
+        .stall_timeout(Duration::from_millis(100))
+        .check()?
+        .run()
+        .wait()
+        .await;
+    drop(held);
+    match result {
+        Err(ModelError::Stalled {
+            stall_timeout,
+            progress,
+        }) => {
+            assert_eq!(stall_timeout, Duration::from_millis(100));
+            assert_eq!(progress.len(), 2);
+        }
+        _ => panic!("the stalled model is not detected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn unconnected() {
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    let idle: Actor<_, 0, 0> = (Logging::<f64>::default(), "idle").into();
+    source
+        .add_output()
+        .build::<Vec<f64>, Sig>()
+        .into_input(&mut sink);
+    let check = Model::new(vec![Box::new(source), Box::new(sink), Box::new(idle)]).check();
+    assert!(matches!(check, Err(ModelError::Unconnected(actor)) if actor == "idle"));
+}
@@ -0,0 +1,43 @@
+//! Reproducibility of the stochastic runs seeded with Model::seed
+
+use dos_actors::prelude::*;
+
+enum Noise {}
+
+async fn run(seed: u64) -> anyhow::Result<Vec<f64>> {
+    let mut source: Initiator<_> = Signals::new(2, 100).signals(Signal::white_noise()?).into();
+    let mut other_source: Initiator<_> = Signals::new(1, 100)
+        .signals(Signal::white_noise()?.std_dev(2.)?)
+        .into();
+    let logging = Logging::<f64>::default().n_entry(2).into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source
+        .add_output()
+        .build::<Vec<f64>, Noise>()
+        .into_input(&mut sink);
+    other_source
+        .add_output()
+        .build::<Vec<f64>, Noise>()
+        .into_input(&mut sink);
+    Model::new(vec![
+        Box::new(source),
+        Box::new(other_source),
+        Box::new(sink),
+    ])
+    .seed(seed)
+    .check()?
+    .run()
+    .wait()
+    .await?;
+    let data = logging.lock().await.to_vec();
+    Ok(data)
+}
+
+#[tokio::test]
+async fn model_seed() -> anyhow::Result<()> {
+    let data = run(42).await?;
+    assert_eq!(data.len(), 300);
+    assert_eq!(data, run(42).await?);
+    assert_ne!(data, run(7).await?);
+    Ok(())
+}
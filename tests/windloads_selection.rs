@@ -0,0 +1,39 @@
+//! CFD wind loads group selection test
+//!
+//! Only the M1 baffle loads are applied, all the other OSS loads must be zero
+//! The CFD loads are read from the `parse-monitors` CFD baseline repository
+
+use dos_actors::clients::windloads::{CfdLoads, MountLoads, WindLoads, WindLoads::*, CS};
+use dos_actors::io::Write;
+use parse_monitors::cfd;
+
+#[test]
+fn windloads_selection() -> anyhow::Result<()> {
+    let cfd_case = cfd::CfdCase::<2021>::colloquial(30, 0, "os", 7)?;
+    let cfd_path = cfd::Baseline::<2021>::path().join(cfd_case.to_string());
+
+    let loads = vec![TopEnd, M2Baffle, Trusses, M1Baffle, MirrorCovers];
+    let keys: Vec<String> = loads.iter().flat_map(|x| x.keys()).collect();
+    let locations: Vec<CS> = keys.iter().map(|_| CS::OSS(vec![0f64; 3])).collect();
+
+    let mut cfd_loads = CfdLoads::zoh(cfd_path.to_str().unwrap())
+        .duration(1f64)
+        .nodes(keys.clone(), locations)
+        .select(vec![M1Baffle])
+        .build()?;
+
+    let mut n_sample = 0;
+    while let Some(data) = <CfdLoads<_> as Write<Vec<f64>, MountLoads>>::write(&mut cfd_loads) {
+        for (key, fm) in keys.iter().zip(data.chunks(6)) {
+            if WindLoads::from_key(key) == Some(M1Baffle) {
+                assert!(fm.iter().any(|x| *x != 0f64));
+            } else {
+                assert!(fm.iter().all(|x| *x == 0f64), "{key} loads are not zero");
+            }
+        }
+        n_sample += 1;
+    }
+    assert!(n_sample > 0);
+
+    Ok(())
+}